@@ -1,15 +1,14 @@
 use anyhow::Result;
 use gflags_derive::GFlags;
 use serde::{Deserialize, Serialize};
-use std::collections::HashSet;
 use std::fs::File;
 use std::io::BufReader;
-use std::iter::FromIterator;
 use std::path::Path;
 
 #[derive(Clone, Debug, Default, Deserialize, Serialize, GFlags)]
 #[serde(rename_all = "kebab-case")]
 #[serde(default)]
+#[gflags(generate_help_handler)]
 struct Config {
     /// Path to configuration file to load
     config_file: String,
@@ -56,10 +55,7 @@ impl Config {
 }
 
 fn main() -> Result<()> {
-    let args: HashSet<&'static str> = HashSet::from_iter(gflags::parse().iter().cloned());
-    if args.contains("help") {
-        gflags::print_help_and_exit(0);
-    }
+    Config::handle_help();
 
     let c = Config::new().expect("Config did not parse");
 
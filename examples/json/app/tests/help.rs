@@ -0,0 +1,16 @@
+use std::process::Command;
+
+#[test]
+fn help_prints_flags_and_exits() {
+    let output = Command::new(env!("CARGO_BIN_EXE_app"))
+        .arg("help")
+        .output()
+        .expect("Failed to run app");
+
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("--config-file"));
+    assert!(stdout.contains("--pw-length"));
+    assert!(!stdout.contains("Suggested password"));
+}
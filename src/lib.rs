@@ -57,7 +57,8 @@
 //! Note that:
 //!
 //! - The comment on each struct field is also the documentation comment for
-//!   the flag, which becomes its help text.
+//!   the flag, which becomes its help text. The comment is used verbatim, so
+//!   Markdown such as `[text](url)` links appears in the help as written.
 //! - The type for the `--dir` flag has been converted from `String` to `&str`.
 //!
 //! # Defining a flag prefix
@@ -128,659 +129,6324 @@
 //! }
 //! ```
 //!
-//! # Handling `Option<T>`
-//!
-//! Your configuration `struct` may have fields that have `Option<T>` types.
-//! For these fields `gflags_derive` creates a flag of the inner type `T`.
+//! To choose the separator independently of the prefix use the
+//! `#[gflags(separator = "...")]` attribute on the struct. The separator must
+//! be `-` or `_`, as `gflags` does not accept any other characters between
+//! the words of a flag name. This takes precedence over the separator at the
+//! end of the prefix, and can be used without a prefix.
 //!
-//! # Customising the default value
+//! ```ignore
+//! use gflags_derive::GFlags;
 //!
-//! To specify a default value for the flag add a `#[gflags(default = ...)]`
-//! attribute to the field.
+//! #[derive(GFlags)]
+//! #[gflags(prefix = "log", separator = "_")]
+//! struct Config {
+//!     /// True if log messages should also be sent to STDERR
+//!     to_stderr: bool,
+//! }
+//! ```
 //!
-//! The value for the attribute is the literal value, not a quoted value.
-//! Only quote the value if the type of the field is a string or can be
-//! created from a string.
+//! generates a `--log_to_stderr` flag.
 //!
-//! For example, to set the default value of the `--log-to-stderr` flag to
-//! `true`:
+//! To use a different separator after the prefix than between the words of the
+//! field name add `#[gflags(prefix_separator = "...")]`,
+//! `#[gflags(word_separator = "...")]`, or both, to the struct. Each takes
+//! precedence over `separator` for its part of the name, and must also be `-`
+//! or `_`. The separator after the namespace is the same as the one after the
+//! prefix.
 //!
 //! ```ignore
 //! use gflags_derive::GFlags;
 //!
 //! #[derive(GFlags)]
-//! #[gflags(prefix = "log-")]
+//! #[gflags(prefix = "log", prefix_separator = "_", word_separator = "-")]
 //! struct Config {
 //!     /// True if log messages should also be sent to STDERR
-//!     #[gflags(default = true)]
 //!     to_stderr: bool,
+//! }
+//! ```
+//!
+//! generates a `--log_to-stderr` flag.
+//!
+//! If a field name already starts with the prefix, like `log_dir` with the
+//! prefix `log-`, the flag is `--log-log-dir`. To remove the prefix from the
+//! start of field names add a `#[gflags(dedup_prefix)]` attribute to the
+//! struct, and the flag is `--log-dir`.
+//!
+//! ```ignore
+//! use gflags_derive::GFlags;
+//!
+//! #[derive(GFlags)]
+//! #[gflags(prefix = "log-", dedup_prefix)]
+//! struct Config {
+//!     /// The directory to write log files to
+//!     log_dir: String,
+//! }
+//! ```
+//!
+//! To change field names before they are used in flag names add a
+//! `#[gflags(transform = "...")]` attribute to the struct, with one of
+//! these built-in transforms:
+//!
+//! - `strip_config_suffix`, remove a trailing `_config` (e.g., `log_config`
+//!   is `--log`)
+//! - `strip_opt_prefix`, remove a leading `opt_` (e.g., `opt_dir` is
+//!   `--dir`)
+//! - `trim_underscores`, remove leading and trailing `_` (e.g., `_dir` is
+//!   `--dir`)
+//!
+//! Give the attribute more than once to apply several transforms, in
+//! order. A transform that would remove the whole field name is ignored.
+//!
+//! Flag names may only contain ASCII lowercase letters, digits, and the
+//! separator, and it is a compile time error if the prefix or a field name
+//! would generate a flag name containing anything else. `gflags` also needs
+//! each `-` separated word to start with a letter, so a field like `scale_2x`
+//! is an error unless the flag is renamed.
+//!
+//! Flags are registered globally, so two crates that define flags with the
+//! same name only find out when the program runs. To keep a crate's flags
+//! apart from other crates' add a `#[gflags(namespace = "...")]` attribute
+//! to the struct, usually with the crate's name. The namespace is added
+//! before the prefix, to the flag names and the names of the flag
+//! variables.
 //!
+//! ```ignore
+//! use gflags_derive::GFlags;
+//!
+//! #[derive(GFlags)]
+//! #[gflags(namespace = "mylib", prefix = "log-")]
+//! struct Config {
 //!     /// The directory to write log files to
 //!     dir: String,
 //! }
 //! ```
 //!
-//! Specifying this with quotes, `#[gflags(default = "true")]` will give a
-//! compile time error:
+//! generates a `--mylib-log-dir` flag, in the `MYLIB_LOG_DIR` variable.
 //!
-//! ```text
-//! expected `bool`, found `&str`
+//! # Repeating a configuration
+//!
+//! There is no `#[gflags(flatten)]` attribute, so a struct can not include
+//! the flags of another struct, and can not include them more than once
+//! with an index in the prefix (e.g., `--log0-dir` and `--log1-dir`). The
+//! derive only sees the tokens of the struct it is attached to, not the
+//! fields of the types it contains, and each flag is a `static`, so
+//! expanding the same struct twice would define the same flags twice.
+//!
+//! To get repeated flags give each instance its own struct and prefix.
+//!
+//! ```ignore
+//! use gflags_derive::GFlags;
+//!
+//! #[derive(GFlags)]
+//! #[gflags(prefix = "log0-")]
+//! struct PrimaryLogConfig {
+//!     /// The directory to write primary log files to
+//!     dir: String,
+//! }
+//!
+//! #[derive(GFlags)]
+//! #[gflags(prefix = "log1-")]
+//! struct SecondaryLogConfig {
+//!     /// The directory to write secondary log files to
+//!     dir: String,
+//! }
 //! ```
 //!
-//! > **Important**: This does *not* change the default value when an instance
-//! of the `Config` struct is created. It only changes the default value of
-//! the `LOG_TO_STDERR.flag` variable.
+//! For the same reason the flags of a struct from another crate can not be
+//! given a different prefix (e.g., to expose a library's `--log-dir` flag
+//! as `--app-log-dir`). The library's flags are defined when the library is
+//! compiled, with the library's prefix. To re-prefix them, derive flags for
+//! a struct in the application with the prefix you want, and copy the
+//! values into the library's configuration.
 //!
-//! # Customising the type
+//! Deriving `GFlags` twice for a struct, for example with
+//! `#[derive(GFlags, GFlags)]`, or with a derive added both by hand and by
+//! `prost-build`, also defines the same flags twice. Each expansion of the
+//! derive is independent, so this is reported by the compiler, as a name that
+//! is defined multiple times. Within a single expansion the derive reports
+//! two fields that would generate the same flag. If two structs need flags
+//! with the same field names give them different prefixes, or a
+//! `#[gflags(namespace = "...")]`.
 //!
-//! To use a different type for the field and the command line flag add a
-//! `#[gflags(type = "...")]` attribute to the field.  For example, to store
-//! the log directory as a `PathBuf` but accept a string on the command line:
+//! # Renaming a flag
+//!
+//! To use a different name for a flag than the field's name add a
+//! `#[gflags(name = "...")]` attribute to the field. `#[gflags(rename = "...")]`
+//! is a synonym, for familiarity with `serde`. Use one or the other, not both.
+//!
+//! The name replaces the field's name, so any prefix is still applied, but
+//! transforms and `dedup_prefix` are not.
 //!
 //! ```ignore
 //! use gflags_derive::GFlags;
-//! use std::path::PathBuf;
 //!
 //! #[derive(GFlags)]
 //! #[gflags(prefix = "log-")]
 //! struct Config {
 //!     /// True if log messages should also be sent to STDERR
+//!     #[gflags(rename = "stderr")]
 //!     to_stderr: bool,
+//! }
+//! ```
 //!
+//! This generates the `--log-stderr` flag, in the `LOG_STDERR` variable.
+//!
+//! # Changing the case of flag names
+//!
+//! The prefix is converted to lowercase, so `#[gflags(prefix = "LOG_")]` gives
+//! flags like `--log_dir`. Flag names keep the case of the field name. To
+//! convert the complete flag name, including the prefix, to lowercase or
+//! uppercase add a `#[gflags(lowercase)]` or `#[gflags(uppercase)]`
+//! attribute to the struct.
+//!
+//! ```ignore
+//! use gflags_derive::GFlags;
+//!
+//! #[derive(GFlags)]
+//! #[gflags(prefix = "Log-", lowercase)]
+//! #[allow(non_snake_case)]
+//! struct Config {
 //!     /// The directory to write log files to
-//!     #[gflags(type = "&str")]
-//!     dir: PathBuf,
+//!     logDir: String,
 //! }
 //! ```
 //!
-//! # Customising the visibility
+//! generates a `--log-logdir` flag.
 //!
-//! To use a different visibility for the flags add a
-//! `#[gflags(visibility = "...")]` attribute to the field and give a Rust
-//! visibility specifier.
+//! With `#[gflags(uppercase)]` flag names must contain uppercase letters
+//! instead of lowercase letters.
 //!
-//! In this example the `LOG_DIR` flag variable will be visible in the parent
-//! module.
+//! To split field names that are not `snake_case` into words add a
+//! `#[gflags(acronyms = "...")]` attribute to the struct, with a comma
+//! separated list of acronyms. Field names are then split at `_` and at
+//! changes of case, each acronym is kept as a single word, and the words are
+//! converted to lowercase. Without `OAuth` in the list `getOAuthToken` would
+//! be split into `get`, `o`, `auth`, and `token`.
 //!
 //! ```ignore
 //! use gflags_derive::GFlags;
-//! use std::path::PathBuf;
 //!
 //! #[derive(GFlags)]
-//! #[gflags(prefix = "log-")]
+//! #[gflags(acronyms = "HTTPS, OAuth")]
+//! #[allow(non_snake_case)]
 //! struct Config {
-//!     /// True if log messages should also be sent to STDERR
-//!     to_stderr: bool,
+//!     /// The proxy to use
+//!     HTTPSProxy: String,
 //!
-//!     /// The directory to write log files to
-//!     #[gflags(visibility = "pub(super)")]
-//!     #[gflags(type = "&str")]
-//!     dir: PathBuf,
+//!     /// The token to authenticate with
+//!     getOAuthToken: String,
 //! }
 //! ```
 //!
-//! # Specifying a placeholder
+//! generates the `--https-proxy` and `--get-oauth-token` flags. The names of
+//! `enum` variants are always split this way, and also use the acronyms.
 //!
-//! To give a placeholder that will appear in the flag's `help` output add a
-//! `#[gflags(placeholder = "...")]` attribute to the field. This will be
-//! wrapped in `<...>` for display.
+//! # Handling `Option<T>`
+//!
+//! Your configuration `struct` may have fields that have `Option<T>` types.
+//! For these fields `gflags_derive` creates a flag of the inner type `T`.
+//!
+//! If `T` is a reference, like `Option<&'a str>`, the flag has the same type
+//! without the lifetime, `&str`.
+//!
+//! Other single-generic wrapper types can be unwrapped in the same way by
+//! adding a `#[gflags(unwrap = "...")]` attribute to the struct for each
+//! wrapper type. The value must match the path used in the field's type.
 //!
 //! ```ignore
 //! use gflags_derive::GFlags;
-//! use std::path::PathBuf;
 //!
 //! #[derive(GFlags)]
-//! #[gflags(prefix = "log-")]
+//! #[gflags(prefix = "log-", unwrap = "Maybe")]
 //! struct Config {
-//!     /// True if log messages should also be sent to STDERR
-//!     to_stderr: bool,
-//!
 //!     /// The directory to write log files to
-//!     #[gflags(placeholder = "DIR")]
-//!     #[gflags(type = "&str")]
-//!     dir: PathBuf,
+//!     dir: Maybe<String>,
 //! }
 //! ```
 //!
-//! In the help output the `--log-dir` flag will appear as:
+//! generates a `--log-dir` flag with type `&str`.
 //!
-//! ```text
-//! --log-dir <DIR>
-//!         The directory to write log files to
-//! ```
+//! `apply_flags()`, described in "Applying flags" below, can not construct
+//! these wrapper types, so `#[gflags(generate_apply)]` is an error if a field
+//! has one. Skip the field with `#[gflags(skip)]` and set it yourself.
 //!
-//! # Skipping flags
+//! # Handling `Duration`
 //!
-//! To skip flag generation for a field add a `#[gflags(skip)]` attribute to
-//! the field.
+//! Fields with a `std::time::Duration` type, or `Option<Duration>`, have a
+//! `&str` flag, as `gflags` can not parse a `Duration`. The
+//! `apply_flags()` method, described in "Applying flags" below, parses the
+//! value into the field.
+//!
+//! A duration is one or more numbers, each followed by a unit, with no
+//! spaces: `ms` for milliseconds, `s` for seconds, `m` for minutes, `h` for
+//! hours, and `d` for days. For example, `30s`, `1h30m`, or `1500ms`. If the
+//! value is not valid the program exits with an error.
 //!
 //! ```ignore
 //! use gflags_derive::GFlags;
-//! use std::path::PathBuf;
+//! use std::time::Duration;
 //!
 //! #[derive(GFlags)]
-//! #[gflags(prefix = "log-")]
+//! #[gflags(prefix = "db-", generate_apply)]
 //! struct Config {
-//!     /// True if log messages should also be sent to STDERR
-//!     to_stderr: bool,
+//!     /// How long to wait for a connection
+//!     #[gflags(default = "10s")]
+//!     timeout: Duration,
+//! }
+//! ```
 //!
-//!     /// The directory to write log files to
-//!     #[gflags(skip)]
-//!     dir: PathBuf,
+//! # Handling lists
+//!
+//! `gflags` can not parse a `Vec<T>`. To have a flag that is a list of values
+//! add a `#[gflags(delimiter = "...")]` attribute to a `Vec<T>` (or
+//! `Option<Vec<T>>`) field.
+//!
+//! The flag's type is a generated list type, named after the flag, that splits
+//! the value on the delimiter and parses each item with `FromStr`. For example,
+//! the `--log-tags` flag below has the type `LogTagsList`, a wrapper around a
+//! `Vec<String>`. An empty value is an empty list. The `apply_flags()` method,
+//! described in "Applying flags" below, copies the list into the field.
+//!
+//! A flag with a delimiter can not have a default value.
+//!
+//! ```ignore
+//! use gflags_derive::GFlags;
+//!
+//! #[derive(GFlags)]
+//! #[gflags(prefix = "log-", generate_apply)]
+//! struct Config {
+//!     /// Tags to add to each log line
+//!     #[gflags(delimiter = ",")]
+//!     tags: Vec<String>,
 //! }
 //! ```
 //!
-//! No `--log-dir` flag will be generated.
+//! # Restricting the values
 //!
-//! # Providing multiple attributes
+//! To only accept some values for a flag add a `#[gflags(choices = "...")]`
+//! attribute to the field, with a comma separated list of the values. The
+//! flag's type is a generated type, named after the flag, that only parses
+//! one of the choices. For example, the `--log-level` flag below has the type
+//! `LogLevelChoice`, a wrapper around the matching `&'static str`. The choices
+//! are added to the flag's help as a new line, e.g.,
+//! `[choices: error, warn, info]`.
 //!
-//! If you want to provide multiple attributes on a field then you can mix
-//! and match specifing multiple options in a single `#[gflags(...)]` attribute
-//! and specifying multiple `#[gflags(...)]` attributes. The following examples
-//! are identical.
+//! A default value must be one of the choices. The field must implement
+//! `From<&str>` for `apply_flags()`, described in "Applying flags" below.
 //!
 //! ```ignore
-//! ...
-//!     /// The directory to write log files to
-//!     #[gflags(type = "&str", visibility = "pub(super)")]
-//!     dir: PathBuf,
-//! ...
+//! use gflags_derive::GFlags;
+//!
+//! #[derive(GFlags)]
+//! #[gflags(prefix = "log-")]
+//! struct Config {
+//!     /// Minimum level of messages to log
+//!     #[gflags(choices = "error, warn, info", default = "info")]
+//!     level: String,
+//! }
 //! ```
 //!
+//! # Customising the default value
+//!
+//! To specify a default value for the flag add a `#[gflags(default = ...)]`
+//! attribute to the field.
+//!
+//! The value for the attribute is the literal value, not a quoted value.
+//! Only quote the value if the type of the field is a string or can be
+//! created from a string.
+//!
+//! The value can also be the path to a `const`, or any other expression
+//! that Rust can promote to a `'static` reference, such as `-1` or
+//! `Level::Info`.
+//!
+//! Numbers may have a type suffix, like `100u64`. It is a compile time
+//! error if the suffix is not the flag's type.
+//!
+//! For example, to set the default value of the `--log-to-stderr` flag to
+//! `true`:
+//!
 //! ```ignore
-//! ...
+//! use gflags_derive::GFlags;
+//!
+//! #[derive(GFlags)]
+//! #[gflags(prefix = "log-")]
+//! struct Config {
+//!     /// True if log messages should also be sent to STDERR
+//!     #[gflags(default = true)]
+//!     to_stderr: bool,
+//!
 //!     /// The directory to write log files to
-//!     #[gflags(type = "&str")]
-//!     #[gflags(visibility = "pub(super)")]
-//!     dir: PathBuf,
-//! ...
+//!     dir: String,
+//! }
 //! ```
 //!
-//! # Deserializing and merging flags
+//! Using a `const`:
 //!
-//! This supports a powerful pattern for configuring an application that is
-//! composed of multiple crates, where each crate exports a configuration and
-//! supports multiple flags, and the application crate defines a configuration
-//! that imports the configuration structs from the component crates.
+//! ```ignore
+//! use gflags_derive::GFlags;
 //!
-//! This master configuration can be deserialized from e.g. a JSON file, and
-//! then each component crate can have the opportunity to override the loaded
-//! configuration with information from the command line flags that are specific
-//! to that crate.
+//! const DEFAULT_MAX_SIZE: u32 = 64 * 1024;
 //!
-//! See the `examples/json` directory for a complete application that does
-//! this.
+//! #[derive(GFlags)]
+//! #[gflags(prefix = "log-")]
+//! struct Config {
+//!     /// Maximum size of a log file, in KiB
+//!     #[gflags(default = DEFAULT_MAX_SIZE)]
+//!     max_size: u32,
+//! }
+//! ```
 //!
-//! # Use with `prost`
+//! Specifying this with quotes, `#[gflags(default = "true")]` will give a
+//! compile time error:
 //!
-//! This macro can be used to derive flags for `structs` generated from
-//! Protobuffer schemas using `prost` and `prost-build`.
+//! ```text
+//! expected `bool`, found `&str`
+//! ```
 //!
-//! Given this `.proto` file
+//! To compute the default value use a `#[gflags(default_expr = "...")]`
+//! attribute, with the expression as a quoted string. The expression must be
+//! a constant expression, as the value is stored in a `const`. For example,
+//! to use a different default on each platform:
 //!
-//! ```proto
-//! syntax = "proto3"
+//! ```ignore
+//! use gflags_derive::GFlags;
 //!
-//! package log.config.v1;
+//! #[derive(GFlags)]
+//! #[gflags(prefix = "log-")]
+//! struct Config {
+//!     /// The directory to write log files to
+//!     #[gflags(default_expr = r#"if cfg!(windows) { "C:\\logs" } else { "/tmp" }"#)]
+//!     dir: String,
+//! }
+//! ```
 //!
-//! message Config {
-//!     // True if log messages should also be sent to STDERR
-//!     bool to_stderr = 1;
+//! This works for `bool` flags too, for example to turn a flag on by
+//! default when a Cargo feature is enabled:
 //!
-//!     // The directory to write log files to
-//!     string dir = 2;
+//! ```ignore
+//! use gflags_derive::GFlags;
+//!
+//! #[derive(GFlags)]
+//! #[gflags(prefix = "log-")]
+//! struct Config {
+//!     /// True if log messages should be verbose
+//!     #[gflags(default_expr = r#"cfg!(feature = "verbose")"#)]
+//!     verbose: bool,
 //! }
 //! ```
 //!
-//! This `build.rs` file will add the relevant attributes to add the `log-`
-//! prefix and skip the `dir` field.
+//! The expression can call a function, e.g.,
+//! `#[gflags(default_expr = "default_max_size()")]`, if it is a `const fn`. The
+//! function is called when the program is compiled, not when the flags are
+//! parsed, and calling a function that is not `const` is a compile time error.
 //!
 //! ```ignore
-//! fn main() {
-//!     let mut config = prost_build::Config::new();
-//!
-//!     config.type_attribute(".log.config.v1.Config", "#[derive(gflags_derive::GFlags)]");
-//!     config.type_attribute(".log.config.v1.Config", "#[gflags(prefix=\"log-\")]");
+//! use gflags_derive::GFlags;
 //!
-//!     config.field_attribute(".log.config.v1.Config.dir", "#[gflags(skip)]");
+//! const fn default_max_size() -> u32 {
+//!     64 * 1024
+//! }
 //!
-//!     config
-//!         .compile_protos(&["proto/log/config/v1/config.proto"], &["proto"])
-//!         .unwrap();
+//! #[derive(GFlags)]
+//! #[gflags(prefix = "log-")]
+//! struct Config {
+//!     /// Maximum size of a log file, in KiB
+//!     #[gflags(default_expr = "default_max_size()")]
+//!     max_size: u32,
 //! }
 //! ```
 //!
-//! See the `examples/protobuf` directory for a complete application that
-//! does this.
-
+//! A default can not be another flag's value, e.g.,
+//! `#[gflags(default_expr = "LOG_DIR.flag")]`. Defaults are computed when
+//! the program is compiled, before any flags are parsed, and a `const` can
+//! not refer to a `static`, so this fails to compile whatever order the
+//! flags are defined in. To give several flags the same default, define a
+//! `const` and use it in each flag's default. To default to another flag's
+//! value when the program runs, check `is_present()` when applying flags.
+//!
+//! ```ignore
+//! use gflags_derive::GFlags;
+//!
+//! const DEFAULT_DIR: &str = "/tmp";
+//!
+//! #[derive(GFlags)]
+//! #[gflags(prefix = "log-")]
+//! struct Config {
+//!     /// The directory to write log files to
+//!     #[gflags(default = DEFAULT_DIR)]
+//!     dir: String,
+//!
+//!     /// The directory to write archived log files to
+//!     #[gflags(default_expr = "DEFAULT_DIR")]
+//!     archive_dir: String,
+//! }
+//!
+//! let archive_dir = if LOG_ARCHIVE_DIR.is_present() {
+//!     LOG_ARCHIVE_DIR.flag
+//! } else {
+//!     LOG_DIR.flag
+//! };
+//! ```
+//!
+//! For the same reason a default can not be read from a lazily initialized
+//! global, like a `OnceLock` or a `lazy_static!`, e.g.,
+//! `#[gflags(default_expr = "&*DEFAULT_DIR")]`. Dereferencing the global runs
+//! code when the program runs, which a `const` can not do, and the compiler
+//! reports this at the expression. To compute an expensive default once, leave
+//! the flag without a default and put the value in the struct instead, for
+//! example in its `Default` implementation. `apply_flags()` only changes the
+//! field if the flag is present.
+//!
+//! ```ignore
+//! use gflags_derive::GFlags;
+//! use std::sync::OnceLock;
+//!
+//! fn default_dir() -> &'static str {
+//!     static DEFAULT_DIR: OnceLock<String> = OnceLock::new();
+//!     DEFAULT_DIR.get_or_init(|| compute_default_dir())
+//! }
+//!
+//! #[derive(GFlags)]
+//! #[gflags(prefix = "log-", generate_apply)]
+//! struct Config {
+//!     /// The directory to write log files to
+//!     dir: String,
+//! }
+//!
+//! impl Default for Config {
+//!     fn default() -> Self {
+//!         Self { dir: default_dir().to_string() }
+//!     }
+//! }
+//!
+//! let mut config = Config::default();
+//! config.apply_flags();
+//! ```
+//!
+//! Defaults can also be given in one place with a
+//! `#[gflags(defaults(...))]` attribute on the struct, listing field names
+//! and their default values. A `#[gflags(default = ...)]` attribute on a
+//! field takes precedence over a default for that field on the struct.
+//!
+//! ```ignore
+//! use gflags_derive::GFlags;
+//!
+//! #[derive(GFlags)]
+//! #[gflags(prefix = "log-", defaults(to_stderr = true, dir = "/tmp"))]
+//! struct Config {
+//!     /// True if log messages should also be sent to STDERR
+//!     to_stderr: bool,
+//!
+//!     /// The directory to write log files to
+//!     dir: String,
+//! }
+//! ```
+//!
+//! Raw strings, such as `#[gflags(default = r"C:\logs")]`, are passed through
+//! unchanged. Byte strings are rejected, as `gflags` has no flag type that can
+//! hold them.
+//!
+//! > **Important**: This does *not* change the default value when an instance
+//! > of the `Config` struct is created. It only changes the default value of
+//! > the `LOG_TO_STDERR.flag` variable.
+//!
+//! The reverse is not possible either: a flag's default can not be taken
+//! from the struct's `Default` impl, as flag defaults must be constants and
+//! `Default::default()` is not a `const fn`. To keep them in step define a
+//! `const` for each value and use it in both places.
+//!
+//! ```ignore
+//! use gflags_derive::GFlags;
+//!
+//! const DEFAULT_CHARSET: &str = "ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+//!
+//! #[derive(GFlags)]
+//! #[gflags(prefix = "pw-")]
+//! struct Config {
+//!     /// String to use for password characters
+//!     #[gflags(default = DEFAULT_CHARSET)]
+//!     charset: String,
+//! }
+//!
+//! impl Default for Config {
+//!     fn default() -> Self {
+//!         Config { charset: DEFAULT_CHARSET.to_string() }
+//!     }
+//! }
+//! ```
+//!
+//! # Resetting flags
+//!
+//! There is no way to reset a flag to its default value once it has been
+//! set. `gflags` only updates a flag's value while parsing the command line,
+//! and does not expose a way to write `.flag` or clear `.is_present()`.
+//!
+//! Tests that need flags in a known state should put each set of flags in
+//! its own test binary (e.g., a separate file in `tests/`), as each binary
+//! has its own flag registry. To find out which flags were given, by field
+//! name, see `flag_is_present()` in "Summarising flags" below.
+//!
+//! # Customising the type
+//!
+//! To use a different type for the field and the command line flag add a
+//! `#[gflags(type = "...")]` attribute to the field.  For example, to store
+//! the log directory as a `PathBuf` but accept a string on the command line:
+//!
+//! ```ignore
+//! use gflags_derive::GFlags;
+//! use std::path::PathBuf;
+//!
+//! #[derive(GFlags)]
+//! #[gflags(prefix = "log-")]
+//! struct Config {
+//!     /// True if log messages should also be sent to STDERR
+//!     to_stderr: bool,
+//!
+//!     /// The directory to write log files to
+//!     #[gflags(type = "&str")]
+//!     dir: PathBuf,
+//! }
+//! ```
+//!
+//! The value can be any Rust type, including a qualified path to an associated
+//! type such as `#[gflags(type = "<Dir as Setting>::Flag")]`. The type must
+//! implement `gflags::custom::Value`. Generic types can be written with or
+//! without a turbofish, `Port<u16>` and `Port::<u16>` are the same type. A type
+//! that does not implement `Value`, like `HashMap::<String, u32>`, is a compile
+//! time error.
+//!
+//! Custom types must implement `gflags::custom::Value`. If you already have
+//! a function that parses a `&str` into the type, add a
+//! `#[gflags(parse_with = "...")]` attribute to the field giving the path to
+//! that function. The flag then has a generated type that holds the field's
+//! type, named after the flag with a `Value` suffix, such as `LogLevelValue`
+//! for `--log-level`, and `gflags_derive` implements `Value` for it using the
+//! function. The function must return a `Result` with an error type that
+//! implements `Display`.
+//!
+//! ```ignore
+//! use gflags_derive::GFlags;
+//!
+//! #[derive(GFlags)]
+//! #[gflags(prefix = "log-")]
+//! struct Config {
+//!     /// The level to log at
+//!     #[gflags(parse_with = "Level::from_str")]
+//!     level: Level,
+//! }
+//! ```
+//!
+//! If the type already implements `FromStr` add a `#[gflags(from_str)]`
+//! attribute to the field instead, and the generated type uses
+//! `FromStr::from_str`. The error type must implement `Display`.
+//!
+//! With either attribute the field's type may be from another crate, like
+//! `std::net::Ipv4Addr`, and several fields may have the same type.
+//!
+//! Alternatively, use `#[gflags(type = "&str")]` to accept a string for the
+//! flag and convert it to the field's type in your own code.
+//!
+//! Flag values are stored in `'static` variables, and `apply_flags()` copies
+//! them into the struct, so a type that can not be copied or cloned out of a
+//! flag, like `Box<dyn Trait>`, can not be the flag's type. For these, use
+//! `#[gflags(type = "&str")]` together with `#[gflags(parse_with = "...")]`.
+//! The flag then takes a string, and `apply_flags()` converts it to the field's
+//! type with the function, exiting with an error if the function returns one.
+//!
+//! ```ignore
+//! use gflags_derive::GFlags;
+//!
+//! #[derive(GFlags)]
+//! #[gflags(prefix = "draw-", generate_apply)]
+//! struct Config {
+//!     /// The shape to draw
+//!     #[gflags(type = "&str", parse_with = "parse_shape")]
+//!     shape: Box<dyn Shape>,
+//! }
+//! ```
+//!
+//! If the value does not parse the error names the value and the flag, followed
+//! by the parser's error, e.g. `invalid value "trace" for --log-level: ...`. The
+//! lists and restricted values generated for `delimiter` and `choices` report
+//! errors the same way.
+//!
+//! # Customising the visibility
+//!
+//! To use a different visibility for the flags add a
+//! `#[gflags(visibility = "...")]` attribute to the field and give a Rust
+//! visibility specifier.
+//!
+//! Flags are private by default, whatever the visibility of the field,
+//! including fields declared `pub(in path)`.
+//!
+//! To give the flag the same visibility as its field use
+//! `#[gflags(visibility = "inherit")]`.
+//!
+//! In this example the `LOG_DIR` flag variable will be visible in the parent
+//! module.
+//!
+//! ```ignore
+//! use gflags_derive::GFlags;
+//! use std::path::PathBuf;
+//!
+//! #[derive(GFlags)]
+//! #[gflags(prefix = "log-")]
+//! struct Config {
+//!     /// True if log messages should also be sent to STDERR
+//!     to_stderr: bool,
+//!
+//!     /// The directory to write log files to
+//!     #[gflags(visibility = "pub(super)")]
+//!     #[gflags(type = "&str")]
+//!     dir: PathBuf,
+//! }
+//! ```
+//!
+//! `gflags` can only parse flags into `bool`, `&str`, `&OsStr`, `&Path`,
+//! the integer and floating point types, and types that implement
+//! `gflags::custom::Value`. In particular it can not parse slices, so
+//! `#[gflags(type = "&[u8]")]` is an error. For byte data use `&str` or
+//! `&OsStr` and convert the value after parsing.
+//!
+//! # Adding attributes to the flag
+//!
+//! To add attributes to the flag variable, such as `#[doc(hidden)]` or a
+//! `#[cfg_attr(...)]`, add a `#[gflags(attr = "...")]` attribute to the
+//! field. The value is one or more attributes, and `attr` can be repeated.
+//!
+//! ```ignore
+//! use gflags_derive::GFlags;
+//!
+//! #[derive(GFlags)]
+//! #[gflags(prefix = "log-")]
+//! struct Config {
+//!     /// Internal: Number of log files to keep
+//!     #[gflags(attr = "#[doc(hidden)]")]
+//!     count: u32,
+//! }
+//! ```
+//!
+//! `gflags::define!` ignores attributes other than doc comments, so a flag
+//! with `attr` is defined with `gflags::define!` in a hidden module. The
+//! attributes are put on that module, and on a `use` of the flag variable
+//! from it. The module imports everything from the module the `struct` is
+//! in, so the flag's type and default value must be nameable from there; they
+//! can not be items declared inside a function.
+//!
+//! # Specifying a placeholder
+//!
+//! To give a placeholder that will appear in the flag's `help` output add a
+//! `#[gflags(placeholder = "...")]` attribute to the field. This will be
+//! wrapped in `<...>` for display. `bool` flags take no value, so it is a
+//! compile time error to give them a placeholder.
+//!
+//! ```ignore
+//! use gflags_derive::GFlags;
+//! use std::path::PathBuf;
+//!
+//! #[derive(GFlags)]
+//! #[gflags(prefix = "log-")]
+//! struct Config {
+//!     /// True if log messages should also be sent to STDERR
+//!     to_stderr: bool,
+//!
+//!     /// The directory to write log files to
+//!     #[gflags(placeholder = "DIR")]
+//!     #[gflags(type = "&str")]
+//!     dir: PathBuf,
+//! }
+//! ```
+//!
+//! In the help output the `--log-dir` flag will appear as:
+//!
+//! ```text
+//! --log-dir <DIR>
+//!         The directory to write log files to
+//! ```
+//!
+//! To give every flag a placeholder without adding one to each field add a
+//! `#[gflags(placeholder_from_name)]` attribute to the struct. Flags without a
+//! `placeholder` then use the last word of their name, in upper case, so the
+//! `--log-dir` flag also appears as above. `bool` flags take no value, so do
+//! not get a placeholder.
+//!
+//! # Extending the help
+//!
+//! The derive can not read the documentation of a flag's type, for example
+//! to list the values of an `enum`. To add text like this to the flag's
+//! `help` output add a `#[gflags(help_suffix = "...")]` attribute to the
+//! field. The text is appended to the doc comment as a new line.
+//!
+//! ```ignore
+//! use gflags_derive::GFlags;
+//!
+//! #[derive(GFlags)]
+//! #[gflags(prefix = "log-")]
+//! struct Config {
+//!     /// Minimum level of messages to log
+//!     #[gflags(help_suffix = "One of: error, warn, info, debug, trace")]
+//!     level: String,
+//! }
+//! ```
+//!
+//! In the help output the `--log-level` flag will appear as:
+//!
+//! ```text
+//! --log-level
+//!         Minimum level of messages to log
+//!         One of: error, warn, info, debug, trace
+//! ```
+//!
+//! For longer help text add a `#[gflags(help_file = "...")]` attribute to the
+//! field with the path to a file, relative to the crate's `Cargo.toml`. Each
+//! line of the file is appended to the doc comment, before any
+//! `help_suffix`.
+//!
+//! To wrap long lines in the help text add a `#[gflags(wrap = ...)]`
+//! attribute to the struct, with the maximum number of characters in a line.
+//! Lines are only broken at whitespace. This does not include the
+//! indentation that `gflags` adds when printing the help.
+//!
+//! To indent the second and later lines of each flag's help add a
+//! `#[gflags(help_indent = ...)]` attribute to the struct, with the number of
+//! spaces. This is done after wrapping, and empty lines are not indented.
+//!
+//! To find the field a flag was generated from add a
+//! `#[gflags(annotate_source)]` attribute to the struct. Each flag's help
+//! then ends with a line like `Generated from Config::level`.
+//!
+//! To include the default value in the placeholder add a
+//! `#[gflags(placeholder_shows_default)]` attribute to the struct. Flags
+//! with a placeholder and a default value (from `default` or `defaults`)
+//! then show both, e.g., `--log-dir <DIR=/tmp>`.
+//!
+//! A `bool` flag that defaults to `true` is turned off by putting `no` before
+//! its name, e.g., `--nolog-compress`. To say this in the help add a
+//! `#[gflags(invert_help)]` attribute to the field, with `default = true`.
+//! The first line of the doc comment is then rewritten, so
+//!
+//! ```ignore
+//! use gflags_derive::GFlags;
+//!
+//! #[derive(GFlags)]
+//! #[gflags(prefix = "log-")]
+//! struct Config {
+//!     /// Compress log files
+//!     #[gflags(default = true, invert_help)]
+//!     compress: bool,
+//! }
+//! ```
+//!
+//! appears in the help output as:
+//!
+//! ```text
+//! --log-compress
+//!         Compress log files. Enabled by default, use --nolog-compress to disable
+//! ```
+//!
+//! # Abbreviated flags
+//!
+//! To also generate an abbreviated flag, made from the first letter of each
+//! word in the flag name, add a `#[gflags(abbrev)]` attribute to the field.
+//! Add the attribute to the struct to do this for every field.
+//!
+//! ```ignore
+//! use gflags_derive::GFlags;
+//!
+//! #[derive(GFlags)]
+//! #[gflags(prefix = "log-")]
+//! struct Config {
+//!     /// True if log messages should also be sent to STDERR
+//!     #[gflags(abbrev)]
+//!     to_stderr: bool,
+//! }
+//! ```
+//!
+//! generates both a `--log-to-stderr` flag and a `--lts` flag, with flag
+//! variables `LOG_TO_STDERR` and `LTS`. Both set the same field: the
+//! `apply_flags()`, `validate()`, and `flag_is_present()` methods use whichever
+//! of them is present. Giving both is an error: `validate()` reports it, and
+//! `apply_flags()` prints it and exits.
+//!
+//! It is a compile time error if an abbreviation is the same as the name of
+//! another flag or abbreviation on the struct. No abbreviation is generated
+//! if it would be the same as the flag name.
+//!
+//! To help move from `snake_case` flag names to kebab-case, or back, add a
+//! `#[gflags(both_cases)]` attribute to the struct. Each flag is then also
+//! generated with the other separator, with the same help. The flag variable
+//! has a suffix naming its case, so `--log-dir` also has a `--log_dir` flag
+//! with the variable `LOG_DIR_SNAKE`, and `--log_dir` also has a `--log-dir`
+//! flag with the variable `LOG_DIR_KEBAB`. Like an abbreviation, the flag
+//! with the other separator sets the same field, and the generated methods
+//! use whichever of the flags is present. Giving both is an error.
+//!
+//! # Skipping flags
+//!
+//! To skip flag generation for a field add a `#[gflags(skip)]` attribute to
+//! the field.
+//!
+//! ```ignore
+//! use gflags_derive::GFlags;
+//! use std::path::PathBuf;
+//!
+//! #[derive(GFlags)]
+//! #[gflags(prefix = "log-")]
+//! struct Config {
+//!     /// True if log messages should also be sent to STDERR
+//!     to_stderr: bool,
+//!
+//!     /// The directory to write log files to
+//!     #[gflags(skip)]
+//!     dir: PathBuf,
+//! }
+//! ```
+//!
+//! No `--log-dir` flag will be generated.
+//!
+//! `skip` takes precedence over any other options on the field, even in
+//! the same attribute. With `#[gflags(skip, default = "/tmp")]` no flag
+//! is generated, but the `default` is still parsed.
+//!
+//! Fields that have a flag are never reported as dead code, even if your
+//! code only reads the flag variables and never the fields. Skipped fields
+//! are reported as usual.
+//!
+//! To skip every private field add a `#[gflags(only_pub)]` attribute to the
+//! struct. Flags are then only generated for fields that are `pub`, or
+//! `pub(...)` with any restriction.
+//!
+//! ```ignore
+//! use gflags_derive::GFlags;
+//!
+//! #[derive(GFlags)]
+//! #[gflags(prefix = "log-", only_pub)]
+//! pub struct Config {
+//!     /// True if log messages should also be sent to STDERR
+//!     pub to_stderr: bool,
+//!
+//!     /// Number of messages logged so far
+//!     count: u64,
+//! }
+//! ```
+//!
+//! No `--log-count` flag will be generated.
+//!
+//! To skip every field except a few add a `#[gflags(skip_all)]` attribute to
+//! the struct, and a `#[gflags(skip = false)]` attribute to each field that
+//! should have a flag. `skip = false` also generates a flag for a private
+//! field when the struct has `#[gflags(only_pub)]`.
+//!
+//! ```ignore
+//! use gflags_derive::GFlags;
+//!
+//! #[derive(GFlags)]
+//! #[gflags(prefix = "log-", skip_all)]
+//! struct Config {
+//!     /// True if log messages should also be sent to STDERR
+//!     #[gflags(skip = false)]
+//!     to_stderr: bool,
+//!
+//!     /// Number of messages logged so far
+//!     count: u64,
+//! }
+//! ```
+//!
+//! Only the `--log-to-stderr` flag will be generated.
+//!
+//! A field with `#[serde(flatten)]` holds a struct whose fields serde treats
+//! as fields of the parent. To mirror this, derive `GFlags` on the inner
+//! struct so it defines its own flags, and add a
+//! `#[gflags(respect_serde_flatten)]` attribute to the parent struct to
+//! skip the flattened field.
+//!
+//! ```ignore
+//! use gflags_derive::GFlags;
+//! use serde::Deserialize;
+//!
+//! #[derive(Deserialize, GFlags)]
+//! #[gflags(prefix = "log-")]
+//! struct LogConfig {
+//!     /// The directory to write log files to
+//!     dir: String,
+//! }
+//!
+//! #[derive(Deserialize, GFlags)]
+//! #[gflags(prefix = "app-", respect_serde_flatten)]
+//! struct Config {
+//!     /// The name of the application
+//!     name: String,
+//!
+//!     #[serde(flatten)]
+//!     log: LogConfig,
+//! }
+//! ```
+//!
+//! This generates the `--app-name` and `--log-dir` flags.
+//!
+//! # Requiring documentation
+//!
+//! A flag's doc comment is its help text, and a flag without help text is
+//! unfriendly. To make a missing doc comment a compile time error add a
+//! `#[gflags(require_docs)]` attribute to the struct.
+//!
+//! ```ignore
+//! use gflags_derive::GFlags;
+//!
+//! #[derive(GFlags)]
+//! #[gflags(prefix = "log-", require_docs)]
+//! struct Config {
+//!     /// True if log messages should also be sent to STDERR
+//!     to_stderr: bool,
+//!
+//!     dir: String,
+//! }
+//! ```
+//!
+//! This fails to compile because `dir` has no doc comment. Fields with
+//! `#[gflags(skip)]` do not need a doc comment.
+//!
+//! # Exporting help text
+//!
+//! To access a flag's help text from your own code add a
+//! `#[gflags(export_help)]` attribute to the struct. Each flag then has a
+//! `&str` constant named after the flag variable with a `_HELP` suffix,
+//! containing the lines of the doc comment separated by newlines.
+//!
+//! ```ignore
+//! use gflags_derive::GFlags;
+//!
+//! #[derive(GFlags)]
+//! #[gflags(prefix = "log-", export_help)]
+//! struct Config {
+//!     /// True if log messages should also be sent to STDERR
+//!     to_stderr: bool,
+//! }
+//!
+//! assert_eq!(
+//!     LOG_TO_STDERR_HELP,
+//!     "True if log messages should also be sent to STDERR"
+//! );
+//! ```
+//!
+//! The constant is `pub`, whatever the visibility of the flag variable, so
+//! other modules can show the help for a flag they can not read.
+//!
+//! It is a compile time error if a constant has the same name as another
+//! flag's variable, e.g., `--log-dir-help` and the help for `--log-dir`.
+//!
+//! To find the field each flag was generated from in your own code add a
+//! `#[gflags(export_field_map)]` attribute to the struct. This generates a
+//! `&[(&str, &str)]` constant named after the struct, in upper case, with a
+//! `_FLAG_TO_FIELD` suffix, that pairs the name of each flag with the name of
+//! its field. The constant has the same visibility as the struct.
+//!
+//! ```ignore
+//! use gflags_derive::GFlags;
+//!
+//! #[derive(GFlags)]
+//! #[gflags(prefix = "log-", export_field_map)]
+//! struct LogConfig {
+//!     /// True if log messages should also be sent to STDERR
+//!     to_stderr: bool,
+//! }
+//!
+//! assert_eq!(LOG_CONFIG_FLAG_TO_FIELD, &[("log-to-stderr", "to_stderr")]);
+//! ```
+//!
+//! To show the flags under a header in your own help output add a
+//! `#[gflags(group_header = "...")]` attribute to the struct. This generates a
+//! `(&str, &[&str])` constant named after the struct, in upper case, with a
+//! `_FLAG_GROUP` suffix, that pairs the header with the names of the flags.
+//! The flag names do not change. The constant has the same visibility as the
+//! struct.
+//!
+//! ```ignore
+//! use gflags_derive::GFlags;
+//!
+//! #[derive(GFlags)]
+//! #[gflags(group_header = "Logging")]
+//! struct LogConfig {
+//!     /// True if log messages should also be sent to STDERR
+//!     to_stderr: bool,
+//! }
+//!
+//! assert_eq!(LOG_CONFIG_FLAG_GROUP, ("Logging", &["to-stderr"][..]));
+//! ```
+//!
+//! Both constants list the flags in the order their fields are declared in the
+//! struct, so a help renderer can rely on it.
+//!
+//! # Applying flags
+//!
+//! To copy the flags given on the command line into an instance of the
+//! struct add a `#[gflags(generate_apply)]` attribute to the struct. This
+//! generates an `apply_flags(&mut self)` method that sets each field from
+//! its flag, but only if the flag is present on the command line. Other
+//! fields keep their values, so flags can override a configuration loaded
+//! from a file.
+//!
+//! The flag's value is converted with `Into`, so each field's type must
+//! implement `From` the flag's type (e.g., `String` from `&str`). `Option<T>`
+//! fields are set to `Some(...)` if the flag is present and are otherwise
+//! left alone, so an `Option<bool>` field can tell "not given" apart from
+//! `false`.
+//!
+//! Fields are recognised as `Option<T>` by their type. If a field's type is
+//! an alias for `Option<T>`, add a `#[gflags(optional)]` attribute to the
+//! field (and a `#[gflags(type = "...")]` attribute giving `T`) so the value
+//! is still wrapped in `Some(...)`.
+//!
+//! ```ignore
+//! use gflags_derive::GFlags;
+//!
+//! #[derive(Default, GFlags)]
+//! #[gflags(prefix = "log-", generate_apply)]
+//! struct Config {
+//!     /// True if log messages should also be sent to STDERR
+//!     to_stderr: Option<bool>,
+//!
+//!     /// The directory to write log files to
+//!     dir: String,
+//! }
+//!
+//! let mut config = Config::default();
+//! config.apply_flags();
+//! ```
+//!
+//! To also read flags from environment variables add a
+//! `#[gflags(env = "...")]` attribute to the field, with the name of the
+//! variable, or add a `#[gflags(auto_env)]` attribute to the struct to read
+//! every flag from the variable named after the flag, in upper case with `_`
+//! between words (e.g., `--log-dir` is read from `LOG_DIR`). To add a prefix
+//! to the names of the variables add a `#[gflags(env_prefix = "...")]`
+//! attribute to the struct; the prefix and the name are joined with `_`.
+//!
+//! `apply_flags()` sets a field from its variable only if the flag is not
+//! present on the command line. The value of the variable is parsed with
+//! `FromStr`, unless the flag's type is a reference, like `&str`, so other
+//! flag types must implement it. If the value does not parse the program
+//! exits with an error.
+//!
+//! ```ignore
+//! use gflags_derive::GFlags;
+//!
+//! #[derive(Default, GFlags)]
+//! #[gflags(prefix = "log-", generate_apply, auto_env)]
+//! struct Config {
+//!     /// The directory to write log files to
+//!     dir: String,
+//! }
+//!
+//! // Sets `dir` from `--log-dir` or, if that is not given, `$LOG_DIR`
+//! let mut config = Config::default();
+//! config.apply_flags();
+//! ```
+//!
+//! To read a flag's value from a file, for example a secret that should not
+//! appear on the command line, add a `#[gflags(from_file)]` attribute to the
+//! field. This generates a second flag, with `-file` after the flag's name,
+//! that takes the path to the file. If the flag itself is not present
+//! `apply_flags()` reads the file, and sets the field from its contents,
+//! without any trailing newlines, in the same way as from an environment
+//! variable. The file takes precedence over an environment variable. If the
+//! file can not be read the program exits with an error.
+//!
+//! ```ignore
+//! use gflags_derive::GFlags;
+//!
+//! #[derive(Default, GFlags)]
+//! #[gflags(prefix = "db-", generate_apply)]
+//! struct Config {
+//!     /// The database password
+//!     #[gflags(from_file, secret)]
+//!     password: String,
+//! }
+//!
+//! // Sets `password` from `--db-password` or, if that is not given, the
+//! // contents of the file given to `--db-password-file`
+//! let mut config = Config::default();
+//! config.apply_flags();
+//! ```
+//!
+//! To treat an empty value as "use the default" add a
+//! `#[gflags(default_if_empty = "...")]` attribute to the field. After
+//! setting the fields `apply_flags()` replaces the field's value with this
+//! one if it is empty, whether it was empty before or was set from an empty
+//! flag. The field's type must have an `is_empty()` method.
+//!
+//! ```ignore
+//! use gflags_derive::GFlags;
+//!
+//! #[derive(Default, GFlags)]
+//! #[gflags(prefix = "pw-", generate_apply)]
+//! struct Config {
+//!     /// String to use for password characters
+//!     #[gflags(default_if_empty = "ABCDEFGHIJKLMNOPQRSTUVWXYZ")]
+//!     charset: String,
+//! }
+//!
+//! let mut config = Config::default();
+//! config.apply_flags();
+//! assert_eq!(config.charset, "ABCDEFGHIJKLMNOPQRSTUVWXYZ");
+//! ```
+//!
+//! To combine values set in code with flags add a
+//! `#[gflags(generate_builder)]` attribute to the struct, as well as
+//! `generate_apply`. This generates a builder type named after the struct, with
+//! a `FlagsBuilder` suffix, that has a setter for each field. Its `build()`
+//! method starts from the struct's `Default`, sets the fields that were given,
+//! and then calls `apply_flags()`, so flags that are present override values
+//! set in code. The builder has the same visibility as the struct.
+//!
+//! ```ignore
+//! use gflags_derive::GFlags;
+//!
+//! #[derive(Default, GFlags)]
+//! #[gflags(prefix = "log-", generate_apply, generate_builder)]
+//! struct Config {
+//!     /// The directory to write log files to
+//!     dir: String,
+//! }
+//!
+//! // `dir` is "/var/log", unless `--log-dir` is given
+//! let config = ConfigFlagsBuilder::new()
+//!     .dir("/var/log".to_string())
+//!     .build();
+//! ```
+//!
+//! # Validating flags
+//!
+//! To check the flags given on the command line add a
+//! `#[gflags(generate_validate)]` attribute to the struct. This generates a
+//! `validate(&self)` method that returns `Ok(())`, or `Err` with a message
+//! for every problem found, so they can all be reported at once.
+//!
+//! Add these attributes to fields to say what to check:
+//!
+//! - `#[gflags(required)]`, the flag must be present
+//! - `#[gflags(single)]`, the flag must not be given more than once. By
+//!   default `gflags` allows a repeated flag, and keeps the last value
+//! - `#[gflags(min = ...)]` and `#[gflags(max = ...)]`, if present, the
+//!   flag's value must be in this (inclusive) range
+//! - `#[gflags(min_values = ...)]` and `#[gflags(max_values = ...)]`, if
+//!   present, the number of values in a list flag (see "Handling lists")
+//!   must be in this (inclusive) range
+//! - `#[gflags(requires = "...")]`, if present, the flag for the named
+//!   field must also be present. This can be given more than once
+//! - `#[gflags(conflicts_with = "...")]`, if present, the flag for the named
+//!   field must not be present, for mutually exclusive flags. This can be
+//!   given more than once
+//!
+//! It is a compile time error to use these without
+//! `#[gflags(generate_validate)]` on the struct.
+//!
+//! The range from `min` and `max` is added to the flag's help as a new
+//! line, e.g., `[range: 1..=1024]`, `[range: 1..]` with only `min`, or
+//! `[range: ..=1024]` with only `max`.
+//!
+//! ```ignore
+//! use gflags_derive::GFlags;
+//!
+//! #[derive(GFlags)]
+//! #[gflags(prefix = "log-", generate_validate)]
+//! struct Config {
+//!     /// The directory to write log files to
+//!     #[gflags(required)]
+//!     dir: String,
+//!
+//!     /// Maximum size of a log file, in KiB
+//!     #[gflags(min = 1, max = 1024, requires = "dir")]
+//!     max_size: u32,
+//! }
+//!
+//! if let Err(errors) = config.validate() {
+//!     for error in errors {
+//!         eprintln!("{}", error);
+//!     }
+//!     std::process::exit(1);
+//! }
+//! ```
+//!
+//! # Summarising flags
+//!
+//! To log the flags a program was run with add a
+//! `#[gflags(generate_summary)]` attribute to the struct. This generates a
+//! `flags_summary()` function on the struct that returns the name and value
+//! of each flag, one per line. Flags that were not given on the command line
+//! and have no default value are shown as `<unset>`.
+//!
+//! Values are formatted with `Debug`, so custom flag types must implement
+//! it. To hide the value of a flag, for example one that holds a password,
+//! add a `#[gflags(secret)]` attribute to the field, and the value is shown
+//! as `***`.
+//!
+//! ```ignore
+//! use gflags_derive::GFlags;
+//!
+//! #[derive(GFlags)]
+//! #[gflags(prefix = "db-", generate_summary)]
+//! struct Config {
+//!     /// The database user
+//!     #[gflags(default = "admin")]
+//!     user: String,
+//!
+//!     /// The database password
+//!     #[gflags(secret, default = "hunter2")]
+//!     password: String,
+//! }
+//!
+//! assert_eq!(
+//!     Config::flags_summary(),
+//!     "--db-user: \"admin\"\n--db-password: ***\n"
+//! );
+//! ```
+//!
+//! To get the same information as data add a
+//! `#[gflags(generate_effective)]` attribute to the struct. This generates
+//! an `effective_flags(&self)` method that returns a
+//! `Vec<(String, bool, String)>` with the name of each flag, whether it was
+//! present on the command line, and its value. Values are formatted with
+//! `Display` if the flag's type implements it, and with `Debug` otherwise,
+//! so `--db-user` has the value `admin`, not `"admin"`. Secret and unset
+//! flags are shown as in the summary.
+//!
+//! ```ignore
+//! use gflags_derive::GFlags;
+//!
+//! #[derive(GFlags)]
+//! #[gflags(prefix = "db-", generate_effective)]
+//! struct Config {
+//!     /// The database user
+//!     #[gflags(default = "admin")]
+//!     user: String,
+//! }
+//!
+//! let config = Config { user: DB_USER.flag.to_string() };
+//! assert_eq!(
+//!     config.effective_flags(),
+//!     vec![("db-user".to_string(), false, "admin".to_string())]
+//! );
+//! ```
+//!
+//! To get the values as a map, for example for structured logging, add a
+//! `#[gflags(generate_value_map)]` attribute to the struct. This generates a
+//! `flag_value_strings(&self)` method that returns a
+//! `BTreeMap<String, String>` from the name of each flag to its value,
+//! formatted in the same way.
+//!
+//! To show only the flags that were changed, add a `#[gflags(generate_diff)]`
+//! attribute to the struct. This generates a `non_default_flags(&self)` method
+//! that returns the names of the flags whose value is not equal to their
+//! default value, so each flag's type must implement `PartialEq`. A flag
+//! without a default value is included if it is present on the command line.
+//!
+//! To check whether the flag for a field was given, without naming the flag's
+//! variable, add a `#[gflags(generate_is_present)]` attribute to the struct.
+//! This generates a `flag_is_present(field_name: &str)` function on the
+//! struct that returns `Some(true)` if the flag for the field is present on the
+//! command line, `Some(false)` if it is not, and `None` if the field has no
+//! flag.
+//!
+//! ```ignore
+//! use gflags_derive::GFlags;
+//!
+//! #[derive(GFlags)]
+//! #[gflags(prefix = "db-", generate_is_present)]
+//! struct Config {
+//!     /// The database user
+//!     user: String,
+//! }
+//!
+//! assert_eq!(Config::flag_is_present("user"), Some(false));
+//! assert_eq!(Config::flag_is_present("password"), None);
+//! ```
+//!
+//! # Handling help
+//!
+//! To print the help when the program is run with `help` add a
+//! `#[gflags(generate_help_handler)]` attribute to the struct. This generates
+//! a `handle_help()` function on the struct that calls `gflags::parse()`,
+//! prints the help and exits if `help` is one of the arguments, and otherwise
+//! returns the arguments.
+//!
+//! ```ignore
+//! use gflags_derive::GFlags;
+//!
+//! #[derive(GFlags)]
+//! #[gflags(prefix = "log-", generate_help_handler)]
+//! struct Config {
+//!     /// True if log messages should also be sent to STDERR
+//!     to_stderr: bool,
+//! }
+//!
+//! fn main() {
+//!     let args = Config::handle_help();
+//! }
+//! ```
+//!
+//! Call `handle_help()` on only one struct, as it parses the flags for the
+//! whole program.
+//!
+//! # Generating a `clap` command
+//!
+//! To drive `clap` from the same struct enable the `clap` feature of
+//! `gflags-derive` and add a `#[gflags(generate_clap)]` attribute to the
+//! struct. This generates a `clap_command()` function on the struct that
+//! returns a `clap::Command` with an argument for every flag, with the same
+//! name, help, placeholder, and default value. `bool` flags are switches that
+//! take no value.
+//!
+//! Only literal default values are given to `clap`, values from
+//! `#[gflags(default_expr = ...)]` are not.
+//!
+//! ```ignore
+//! use gflags_derive::GFlags;
+//!
+//! #[derive(GFlags)]
+//! #[gflags(prefix = "log-", generate_clap)]
+//! struct Config {
+//!     /// True if log messages should also be sent to STDERR
+//!     to_stderr: bool,
+//!
+//!     /// The directory to write log files to
+//!     #[gflags(default = "/tmp")]
+//!     dir: String,
+//! }
+//!
+//! let matches = Config::clap_command().get_matches();
+//! ```
+//!
+//! # Providing multiple attributes
+//!
+//! If you want to provide multiple attributes on a field then you can mix
+//! and match specifing multiple options in a single `#[gflags(...)]` attribute
+//! and specifying multiple `#[gflags(...)]` attributes. The following examples
+//! are identical.
+//!
+//! ```ignore
+//! ...
+//!     /// The directory to write log files to
+//!     #[gflags(type = "&str", visibility = "pub(super)")]
+//!     dir: PathBuf,
+//! ...
+//! ```
+//!
+//! ```ignore
+//! ...
+//!     /// The directory to write log files to
+//!     #[gflags(type = "&str")]
+//!     #[gflags(visibility = "pub(super)")]
+//!     dir: PathBuf,
+//! ...
+//! ```
+//!
+//! It is a compile time error to use a keyword `gflags_derive` does not know. If
+//! the attributes are generated, for example by a code generator written for a
+//! newer version of `gflags_derive`, add a `#[gflags(lenient)]` attribute to the
+//! struct, and unknown keywords on the struct and its fields are ignored with a
+//! warning instead. The compiler only shows the warnings on nightly Rust.
+//!
+//! ```ignore
+//! use gflags_derive::GFlags;
+//!
+//! #[derive(GFlags)]
+//! #[gflags(prefix = "log-", lenient)]
+//! struct Config {
+//!     /// The directory to write log files to
+//!     #[gflags(some_future_keyword = "...")]
+//!     dir: String,
+//! }
+//! ```
+//!
+//! # Selecting a mode with an `enum`
+//!
+//! Deriving `GFlags` on an `enum` generates a `bool` flag for each variant, to
+//! select a mode. The flag is named after the variant, converted to the flag's
+//! case, and its help is the variant's doc comment. Every variant must be a
+//! unit variant.
+//!
+//! ```ignore
+//! use gflags_derive::GFlags;
+//!
+//! #[derive(GFlags)]
+//! #[gflags(prefix = "mode-")]
+//! enum Mode {
+//!     /// Run normally
+//!     Normal,
+//!
+//!     /// Report what would be done, without doing it
+//!     DryRun,
+//! }
+//! ```
+//!
+//! generates the `--mode-normal` and `--mode-dry-run` flags, in the
+//! `MODE_NORMAL` and `MODE_DRY_RUN` variables.
+//!
+//! The attributes that name the flags (e.g., `prefix`, `namespace`,
+//! `separator`), `require_docs`, `feature`, and `test_only` apply to an
+//! `enum`. The attributes that generate methods do not.
+//!
+//! # Deriving flags conditionally
+//!
+//! The derive can be made conditional on a feature with `cfg_attr`. Any
+//! `#[gflags(...)]` attributes must be made conditional in the same way,
+//! as they are only recognised when the derive is applied.
+//!
+//! ```ignore
+//! use gflags_derive::GFlags;
+//!
+//! #[cfg_attr(feature = "cli", derive(GFlags))]
+//! #[cfg_attr(feature = "cli", gflags(prefix = "log-"))]
+//! struct Config {
+//!     /// True if log messages should also be sent to STDERR
+//!     to_stderr: bool,
+//! }
+//!
+//! #[cfg(feature = "cli")]
+//! fn apply_flags(config: &mut Config) {
+//!     if LOG_TO_STDERR.is_present() {
+//!         config.to_stderr = LOG_TO_STDERR.flag;
+//!     }
+//! }
+//! ```
+//!
+//! The flag variables only exist when the feature is enabled, so code that
+//! refers to them, like `apply_flags` above, must be gated with the same
+//! `#[cfg(...)]`.
+//!
+//! To derive unconditionally but only generate code when a feature is
+//! enabled add a `#[gflags(feature = "...")]` attribute to the struct. Every
+//! item the derive generates, including the flags and any generated
+//! methods, is then gated with `#[cfg(feature = "...")]`.
+//!
+//! ```ignore
+//! use gflags_derive::GFlags;
+//!
+//! #[derive(GFlags)]
+//! #[gflags(prefix = "log-", feature = "cli")]
+//! struct Config {
+//!     /// True if log messages should also be sent to STDERR
+//!     to_stderr: bool,
+//! }
+//! ```
+//!
+//! To only generate code when compiling tests, so test flags are not in
+//! release binaries, add a `#[gflags(test_only)]` attribute to the struct.
+//! Every item the derive generates is then gated with `#[cfg(test)]`.
+//!
+//! Fields can also be gated with `#[cfg(...)]`. The field's flag, and the
+//! code generated for it in methods like `apply_flags()`, are gated in the same
+//! way, so the struct derives in every configuration.
+//!
+//! ```ignore
+//! use gflags_derive::GFlags;
+//!
+//! #[derive(GFlags)]
+//! #[gflags(prefix = "log-", generate_apply)]
+//! struct Config {
+//!     /// True if log messages should also be sent to syslog
+//!     #[cfg(unix)]
+//!     to_syslog: bool,
+//! }
+//! ```
+//!
+//! # Deserializing and merging flags
+//!
+//! This supports a powerful pattern for configuring an application that is
+//! composed of multiple crates, where each crate exports a configuration and
+//! supports multiple flags, and the application crate defines a configuration
+//! that imports the configuration structs from the component crates.
+//!
+//! This master configuration can be deserialized from e.g. a JSON file, and
+//! then each component crate can have the opportunity to override the loaded
+//! configuration with information from the command line flags that are specific
+//! to that crate.
+//!
+//! See the `examples/json` directory for a complete application that does
+//! this.
+//!
+//! # Using a renamed `gflags`
+//!
+//! The generated code calls `gflags::define!`, so `gflags` must be a
+//! dependency of the crate that derives the flags. If `gflags` is renamed, or
+//! re-exported by another crate, add a `#[gflags(crate_path = "...")]`
+//! attribute to the struct with the path to use instead.
+//!
+//! `gflags::define!` itself refers to `gflags` by name, so `gflags` must still
+//! be a dependency under that name. `crate_path` only changes the paths in the
+//! code this macro generates, such as the `gflags::custom::Value` impls.
+//!
+//! ```ignore
+//! use gflags_derive::GFlags;
+//!
+//! #[derive(GFlags)]
+//! #[gflags(prefix = "log-", crate_path = "my_framework::gflags")]
+//! struct Config {
+//!     /// True if log messages should also be sent to STDERR
+//!     to_stderr: bool,
+//! }
+//! ```
+//!
+//! # Use with `prost`
+//!
+//! This macro can be used to derive flags for `structs` generated from
+//! Protobuffer schemas using `prost` and `prost-build`.
+//!
+//! Given this `.proto` file
+//!
+//! ```proto
+//! syntax = "proto3"
+//!
+//! package log.config.v1;
+//!
+//! message Config {
+//!     // True if log messages should also be sent to STDERR
+//!     bool to_stderr = 1;
+//!
+//!     // The directory to write log files to
+//!     string dir = 2;
+//! }
+//! ```
+//!
+//! This `build.rs` file will add the relevant attributes to add the `log-`
+//! prefix and skip the `dir` field.
+//!
+//! ```ignore
+//! fn main() {
+//!     let mut config = prost_build::Config::new();
+//!
+//!     config.type_attribute(".log.config.v1.Config", "#[derive(gflags_derive::GFlags)]");
+//!     config.type_attribute(".log.config.v1.Config", "#[gflags(prefix=\"log-\")]");
+//!
+//!     config.field_attribute(".log.config.v1.Config.dir", "#[gflags(skip)]");
+//!
+//!     config
+//!         .compile_protos(&["proto/log/config/v1/config.proto"], &["proto"])
+//!         .unwrap();
+//! }
+//! ```
+//!
+//! Each `field_attribute` call adds one attribute, so give several options in
+//! a single `#[gflags(...)]` to keep them together, e.g.
+//! `"#[gflags(type = \"&str\", placeholder = \"DIR\")]"`. `skip` can be
+//! combined with other options in the same way. The field is still skipped,
+//! but the other options are checked, so they can be kept while a flag is
+//! temporarily disabled.
+//!
+//! The generated code is brought in with `include!`, and the derive works on it
+//! in the same way. Comments from the `.proto` file become `#[doc = "..."]`
+//! attributes, which are used as the help text like doc comments, and `prost`'s
+//! own `#[prost(...)]` attributes on the fields are ignored.
+//!
+//! See the `examples/protobuf` directory for a complete application that
+//! does this.
+
 #![doc(html_root_url = "https://docs.rs/gflags-derive/0.1.0")]
 
-extern crate proc_macro;
+extern crate proc_macro;
+
+use crate::FlagCase::{KebabCase, SnakeCase};
+use proc_macro2::{Ident, Literal, Span, TokenStream};
+use proc_macro_error::{abort, abort_call_site, emit_warning, proc_macro_error};
+use quote::{format_ident, quote, ToTokens};
+use std::collections::{HashMap, HashSet};
+use syn::{
+    ext::IdentExt,
+    parse::{Parse, ParseStream, Parser},
+    punctuated::Punctuated,
+    spanned::Spanned,
+    Attribute, Data, DataEnum, DataStruct, Expr, ExprLit, Field, Fields, FieldsNamed,
+    FieldsUnnamed, GenericArgument, Lit, LitStr, Meta, MetaNameValue, NestedMeta, Path,
+    PathArguments, PathSegment, Token, Type, Variant, Visibility,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum FlagCase {
+    SnakeCase,
+    KebabCase,
+}
+
+/// Letter case to apply to the whole flag name
+#[derive(Debug, PartialEq)]
+enum LetterCase {
+    Lowercase,
+    Uppercase,
+}
+
+/// Built-in transformations of field names, applied before the field name
+/// is used in a flag name
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Transform {
+    /// Remove a trailing `_config` (e.g., `log_config` becomes `log`)
+    StripConfigSuffix,
+
+    /// Remove a leading `opt_` (e.g., `opt_dir` becomes `dir`)
+    StripOptPrefix,
+
+    /// Remove leading and trailing `_` (e.g., `_dir` becomes `dir`)
+    TrimUnderscores,
+}
+
+impl Transform {
+    /// Names of the transforms, as written in `#[gflags(transform = "...")]`
+    const NAMES: &'static [(&'static str, Transform)] = &[
+        ("strip_config_suffix", Transform::StripConfigSuffix),
+        ("strip_opt_prefix", Transform::StripOptPrefix),
+        ("trim_underscores", Transform::TrimUnderscores),
+    ];
+
+    /// Returns `field_name` transformed. Never returns an empty string, if
+    /// the transform would remove everything the field name is unchanged.
+    fn apply(self, field_name: &str) -> String {
+        let transformed = match self {
+            Transform::StripConfigSuffix => field_name.strip_suffix("_config"),
+            Transform::StripOptPrefix => field_name.strip_prefix("opt_"),
+            Transform::TrimUnderscores => Some(field_name.trim_matches('_')),
+        };
+        match transformed {
+            Some(name) if !name.is_empty() => name.to_string(),
+            _ => field_name.to_string(),
+        }
+    }
+}
+
+impl FlagCase {
+    /// The character that separates words in a flag name
+    fn separator(&self) -> char {
+        match self {
+            SnakeCase => '_',
+            KebabCase => '-',
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Config {
+    /// Prefix to apply to flag names
+    prefix: String,
+
+    /// Namespace to apply to flag names, before the prefix
+    namespace: Option<String>,
+
+    /// Column to wrap help text at, if any
+    wrap: Option<usize>,
+
+    /// Number of spaces to indent the second and later lines of help text
+    help_indent: Option<usize>,
+
+    /// Prefix to apply to environment variable names, if any
+    env_prefix: Option<String>,
+
+    /// Header to group the flags under in help, if any
+    group_header: Option<String>,
+
+    /// True if every flag should be read from an environment variable
+    auto_env: bool,
+
+    /// Path to the `gflags` crate in generated code
+    crate_path: Path,
+
+    flag_case: FlagCase,
+
+    /// Casing for the separator after the namespace and the prefix, which
+    /// may differ from the separator between the words of the field name
+    prefix_case: FlagCase,
+
+    /// Letter case to apply to flag names, if any
+    letter_case: Option<LetterCase>,
+
+    /// True if every generated flag must have a doc comment
+    require_docs: bool,
+
+    /// True if unknown keywords are warned about instead of rejected
+    lenient: bool,
+
+    /// True if a constant containing each flag's help text should be generated
+    export_help: bool,
+
+    /// True if a constant mapping flag names to field names should be generated
+    export_field_map: bool,
+
+    /// Paths of wrapper types to unwrap like `Option<T>`
+    unwrap: Vec<String>,
+
+    /// Transforms to apply to field names, in order
+    transforms: Vec<Transform>,
+
+    /// Acronyms to keep as single words when splitting names into words
+    acronyms: Vec<String>,
+
+    /// Default values for fields, from `defaults(...)`
+    defaults: Vec<(Ident, TokenStream)>,
+
+    /// True if every flag should also have an abbreviated flag
+    abbrev: bool,
+
+    /// True if every flag should also have a flag with the other separator
+    both_cases: bool,
+
+    /// True if a `flags_summary()` function should be generated
+    generate_summary: bool,
+
+    /// True if a `handle_help()` function should be generated
+    generate_help_handler: bool,
+
+    /// True if a `clap_command()` function should be generated
+    generate_clap: bool,
+
+    /// True if an `effective_flags()` method should be generated
+    generate_effective: bool,
+
+    /// True if a `non_default_flags()` method should be generated
+    generate_diff: bool,
+
+    /// True if a `<STRUCT>FlagsBuilder` type should be generated
+    generate_builder: bool,
+
+    /// True if a `flag_value_strings()` method should be generated
+    generate_value_map: bool,
+
+    /// True if a `flag_is_present()` function should be generated
+    generate_is_present: bool,
+
+    /// True if flags should only be generated for public fields
+    only_pub: bool,
+
+    /// True if flags should only be generated for fields with `skip = false`
+    skip_all: bool,
+
+    /// True if each flag's help should name the field it was generated from
+    annotate_source: bool,
+
+    /// True if an `apply_flags()` method should be generated
+    generate_apply: bool,
+
+    /// True if a `validate()` method should be generated
+    generate_validate: bool,
+
+    /// True if the prefix should be removed from the start of field names
+    dedup_prefix: bool,
+
+    /// True if fields with `#[serde(flatten)]` should be skipped
+    respect_serde_flatten: bool,
+
+    /// True if placeholders should include the flag's default value
+    placeholder_shows_default: bool,
+
+    /// True if flags without a placeholder should get one from their name
+    placeholder_from_name: bool,
+
+    /// Feature that must be enabled for anything to be generated
+    feature: Option<String>,
+
+    /// True if nothing should be generated outside `#[cfg(test)]`
+    test_only: bool,
+
+    /// Name of the struct the flags are generated from
+    struct_name: String,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            prefix: "".to_string(),
+            namespace: None,
+            wrap: None,
+            help_indent: None,
+            env_prefix: None,
+            group_header: None,
+            auto_env: false,
+            crate_path: syn::parse_quote!(gflags),
+            flag_case: KebabCase,
+            prefix_case: KebabCase,
+            letter_case: None,
+            require_docs: false,
+            lenient: false,
+            export_help: false,
+            export_field_map: false,
+            unwrap: vec![],
+            transforms: vec![],
+            acronyms: vec![],
+            defaults: vec![],
+            abbrev: false,
+            both_cases: false,
+            generate_summary: false,
+            generate_help_handler: false,
+            generate_clap: false,
+            generate_effective: false,
+            generate_diff: false,
+            generate_builder: false,
+            generate_value_map: false,
+            generate_is_present: false,
+            only_pub: false,
+            skip_all: false,
+            annotate_source: false,
+            generate_apply: false,
+            generate_validate: false,
+            dedup_prefix: false,
+            respect_serde_flatten: false,
+            placeholder_shows_default: false,
+            placeholder_from_name: false,
+            feature: None,
+            test_only: false,
+            struct_name: "".to_string(),
+        }
+    }
+}
+
+fn impl_gflags_macro(ast: &syn::DeriveInput) -> proc_macro::TokenStream {
+    let fields: Vec<&Field> = match &ast.data {
+        Data::Struct(DataStruct {
+            fields: Fields::Named(FieldsNamed { named: fields, .. }),
+            ..
+        }) => fields.into_iter().collect(),
+        // Fields without an identifier are reported, at the field, when a
+        // flag name is needed for them
+        Data::Struct(DataStruct {
+            fields:
+                Fields::Unnamed(FieldsUnnamed {
+                    unnamed: fields, ..
+                }),
+            ..
+        }) => fields.into_iter().collect(),
+        Data::Enum(DataEnum { variants, .. }) => return impl_enum_flags(ast, variants),
+        _ => abort_call_site!("expected a struct with named fields, or an enum"),
+    };
+
+    let mut config = config_from_attributes(&ast.attrs);
+    config.struct_name = ast.ident.to_string();
+
+    for (ident, _) in &config.defaults {
+        if !fields
+            .iter()
+            .any(|field| field.ident.as_ref() == Some(ident))
+        {
+            abort!(ident, "No field named `{}`", ident);
+        }
+    }
+
+    // Abbreviated flags must not collide with any other flag
+    let mut names: HashMap<String, String> = HashMap::new();
+    let mut abbrevs: Vec<(&Field, String, String)> = vec![];
+    // Static names of the flags and their constants, and what they are for
+    let mut statics: Vec<(&Field, String, String)> = vec![];
+    // Field each flag name was generated from
+    let mut flag_fields: HashMap<String, &Field> = HashMap::new();
+    for field in &fields {
+        let gfa = GFlagsAttribute::from(field.attrs.as_slice());
+        report_unknown(&config, &gfa);
+        if is_skipped(&config, field, &gfa) {
+            continue;
+        }
+        let name = flag_name(&config, field, gfa.name.as_ref());
+        check_flag_name(&config, &field.ident, &name);
+        // Two fields can give the same flag name, e.g., with `rename` or
+        // `dedup_prefix`
+        if let Some(other) = flag_fields.insert(name.clone(), field) {
+            abort!(
+                field.ident,
+                "Flag `--{}` for field `{}` collides with the flag for field `{}`",
+                name,
+                field_ident(field),
+                field_ident(other)
+            );
+        }
+        if gfa.from_file {
+            let file_name = file_flag_name(&config, &name);
+            if let Some(other) = flag_fields.insert(file_name.clone(), field) {
+                abort!(
+                    field.ident,
+                    "Flag `--{}` for field `{}` collides with the flag for field `{}`",
+                    file_name,
+                    field_ident(field),
+                    field_ident(other)
+                );
+            }
+            statics.push((field, static_name(&file_name), format!("`--{}`", file_name)));
+            names.insert(file_name.clone(), file_name);
+        }
+        if config.both_cases {
+            if let Some((other_name, other_static)) = other_case_flag(&name) {
+                if let Some(other) = flag_fields.insert(other_name.clone(), field) {
+                    abort!(
+                        field.ident,
+                        "Flag `--{}` for field `{}` collides with the flag for field `{}`",
+                        other_name,
+                        field_ident(field),
+                        field_ident(other)
+                    );
+                }
+                statics.push((field, other_static, format!("`--{}`", other_name)));
+                names.insert(other_name.clone(), other_name);
+            }
+        }
+        let abbrev = abbreviation(&config, &name);
+        statics.push((field, static_name(&name), format!("`--{}`", name)));
+        if config.export_help {
+            statics.push((
+                field,
+                format!("{}_HELP", static_name(&name)),
+                format!("the help constant for `--{}`", name),
+            ));
+        }
+        if (config.abbrev || gfa.abbrev) && abbrev != name {
+            statics.push((field, static_name(&abbrev), format!("`--{}`", abbrev)));
+            abbrevs.push((field, name.clone(), abbrev));
+        }
+        names.insert(name.clone(), name);
+    }
+    for (field, name, abbrev) in abbrevs {
+        // `names` maps an abbreviation to the flag it abbreviates, and a flag
+        // to itself
+        match names.get(&abbrev) {
+            Some(other) if *other == abbrev => abort!(
+                field.ident,
+                "Abbreviation `--{}` for `--{}` collides with `--{}`",
+                abbrev,
+                name,
+                other
+            ),
+            Some(other) => abort!(
+                field.ident,
+                "Abbreviation `--{}` for `--{}` collides with the abbreviation `--{}` of `--{}`",
+                abbrev,
+                name,
+                abbrev,
+                other
+            ),
+            None => (),
+        }
+        names.insert(abbrev, name);
+    }
+
+    // Different flag names can still give the same static name (e.g.,
+    // `--log-dir-help` and the help constant for `--log-dir`)
+    let mut static_names: HashMap<&str, &str> = HashMap::new();
+    for (field, static_name, source) in &statics {
+        if let Some(other) = static_names.get(static_name.as_str()) {
+            abort!(
+                field.ident,
+                "Static name `{}` for {} collides with {}",
+                static_name,
+                source,
+                other
+            );
+        }
+        static_names.insert(static_name, source);
+    }
+
+    // In field declaration order, which the exported constants and generated
+    // functions keep
+    let mut flags: Vec<GeneratedFlag> = vec![];
+
+    for field in &fields {
+        if let Some(flag) = flag_from_field(&config, field) {
+            flags.push(flag);
+        }
+    }
+
+    let summary = if config.generate_summary {
+        summary_from_flags(ast, &flags)
+    } else {
+        TokenStream::new()
+    };
+
+    let help_handler = if config.generate_help_handler {
+        help_handler(ast, &config.crate_path)
+    } else {
+        TokenStream::new()
+    };
+
+    let field_map = if config.export_field_map {
+        field_map_from_flags(ast, &flags)
+    } else {
+        TokenStream::new()
+    };
+
+    let group = match &config.group_header {
+        Some(header) => group_from_flags(ast, header, &flags),
+        None => TokenStream::new(),
+    };
+
+    let clap = if config.generate_clap {
+        clap_from_flags(ast, &flags)
+    } else {
+        TokenStream::new()
+    };
+
+    let effective = if config.generate_effective {
+        effective_from_flags(ast, &flags)
+    } else {
+        TokenStream::new()
+    };
+
+    let diff = if config.generate_diff {
+        diff_from_flags(ast, &flags)
+    } else {
+        TokenStream::new()
+    };
+
+    let builder = if config.generate_builder {
+        if !config.generate_apply {
+            abort_call_site!(
+                "`#[gflags(generate_builder)]` requires `#[gflags(generate_apply)]` on the struct"
+            );
+        }
+        builder_from_fields(ast, &fields)
+    } else {
+        TokenStream::new()
+    };
+
+    let value_map = if config.generate_value_map {
+        value_map_from_flags(ast, &flags)
+    } else {
+        TokenStream::new()
+    };
+
+    let is_present = if config.generate_is_present {
+        is_present_from_flags(ast, &flags)
+    } else {
+        TokenStream::new()
+    };
+
+    let apply = if config.generate_apply {
+        apply_from_flags(ast, &flags)
+    } else {
+        TokenStream::new()
+    };
+
+    let validate = if config.generate_validate {
+        validate_from_flags(ast, &flags)
+    } else {
+        TokenStream::new()
+    };
+
+    let fields = fields_from_flags(ast, &flags);
+
+    let tokens = flags.iter().map(|flag| &flag.tokens);
+    let gen = quote! {
+        #(#tokens)*
+
+        #fields
+
+        #summary
+
+        #help_handler
+
+        #field_map
+
+        #group
+
+        #clap
+
+        #effective
+
+        #diff
+
+        #builder
+
+        #value_map
+
+        #is_present
+
+        #apply
+
+        #validate
+    };
+
+    gate_from_config(&config, gen).into()
+}
+
+/// Generate a `bool` flag for each unit variant of an `enum`, to select a
+/// mode with e.g., `--mode-dry-run`. The flag name is the variant's name in
+/// the flag's case, with the prefix, and the help is the variant's doc
+/// comment.
+fn impl_enum_flags(
+    ast: &syn::DeriveInput,
+    variants: &Punctuated<Variant, Token![,]>,
+) -> proc_macro::TokenStream {
+    let mut config = config_from_attributes(&ast.attrs);
+    config.struct_name = ast.ident.to_string();
+    let gflags = &config.crate_path;
+
+    let mut variant_names: HashMap<String, &Ident> = HashMap::new();
+    let mut flags = vec![];
+    for variant in variants {
+        if !matches!(variant.fields, Fields::Unit) {
+            abort!(
+                variant.ident,
+                "Flags can only be generated for unit variants of an enum"
+            );
+        }
+        if let Some(attr) = variant
+            .attrs
+            .iter()
+            .find(|attr| attr.path.is_ident("gflags"))
+        {
+            abort!(attr, "`#[gflags(...)]` is not supported on enum variants");
+        }
+
+        // `DryRun` is `dry_run`, before the prefix and separator are applied
+        let variant_name = snake_case_words(&variant.ident.unraw().to_string(), &config.acronyms);
+        let name = prefixed_flag_name(&config, variant_name, None);
+        check_flag_name(&config, &variant.ident, &name);
+        if let Some(other) = variant_names.insert(name.clone(), &variant.ident) {
+            abort!(
+                variant.ident,
+                "Flag `--{}` for variant `{}` collides with the flag for variant `{}`",
+                name,
+                variant.ident,
+                other
+            );
+        }
+
+        let mut docs: Vec<Literal> = vec![];
+        for attr in &variant.attrs {
+            if !attr.path.is_ident("doc") {
+                continue;
+            }
+            if let Ok(Meta::NameValue(MetaNameValue {
+                lit: Lit::Str(lit), ..
+            })) = attr.parse_meta()
+            {
+                docs.push(lit.token());
+            }
+        }
+        if config.require_docs && docs.is_empty() {
+            abort!(
+                variant.ident,
+                "`#[gflags(require_docs)]` requires a doc comment on this variant"
+            );
+        }
+
+        flags.push(define_flag(
+            gflags,
+            FlagStatic {
+                attrs: &[],
+                vis: &TokenStream::new(),
+                ident: format_ident!("{}", static_name(&name)),
+                name: &name,
+                doc: docs,
+                placeholder: None,
+                ty: &quote! { bool },
+                default: None,
+            },
+        ));
+    }
+
+    gate_from_config(&config, quote! { #(#flags)* }).into()
+}
+
+/// Returns the items in `gen` gated on the feature from `config`, if there is
+/// one, and on `test`, if requested.
+fn gate_from_config(config: &Config, gen: TokenStream) -> TokenStream {
+    let mut gates: Vec<Attribute> = vec![];
+    if let Some(feature) = &config.feature {
+        gates.push(syn::parse_quote!(#[cfg(feature = #feature)]));
+    }
+    if config.test_only {
+        gates.push(syn::parse_quote!(#[cfg(test)]));
+    }
+    if gates.is_empty() {
+        gen
+    } else {
+        gate_items(gen, &gates)
+    }
+}
+
+/// Returns the items in `gen` with `attrs` (e.g., `#[cfg(...)]`) added to
+/// each one.
+fn gate_items(gen: TokenStream, attrs: &[Attribute]) -> TokenStream {
+    let file: syn::File = match syn::parse2(gen) {
+        Ok(file) => file,
+        Err(e) => abort_call_site!("Failed to parse generated code: {}", e),
+    };
+    let items = file.items;
+    let attrs = quote! { #(#attrs)* };
+    quote! {
+        #(
+            #attrs
+            #items
+        )*
+    }
+}
+
+/// A flag generated from a field.
+struct GeneratedFlag {
+    /// Name of the flag, without the leading `--`
+    name: String,
+
+    /// Identifier of the flag's `static`
+    ident: Ident,
+
+    /// Names and identifiers of other flags that set the same field, like
+    /// the abbreviation
+    aliases: Vec<(String, Ident)>,
+
+    /// Help text for the flag, from the field's doc comment
+    help: String,
+
+    /// Placeholder to display in the help, if any
+    placeholder: Option<String>,
+
+    /// True if the flag has a default value
+    has_default: bool,
+
+    /// Default value of the flag as text, if it is a literal
+    default_value: Option<String>,
+
+    /// Expression for the default value of the flag, if it has one
+    default: Option<TokenStream>,
+
+    /// True if the flag is a `bool`
+    is_bool: bool,
+
+    /// True if the field is a `Duration`, parsed from a `&str` flag
+    is_duration: bool,
+
+    /// Function that parses the field from a `&str` flag in `apply_flags()`,
+    /// if `parse_with` is used with `type = "&str"`
+    parse_in_apply: Option<Path>,
+
+    /// True if the field is a `Vec<T>`, with a flag of a generated list type
+    is_list: bool,
+
+    /// True if the flag has a generated type that only accepts some values
+    is_choice: bool,
+
+    /// True if the flag has a generated type that holds the field's type,
+    /// parsed with `parse_with` or `from_str`
+    is_parsed: bool,
+
+    /// Environment variable to read the flag from, if any
+    env: Option<String>,
+
+    /// Name and identifier of the `--<name>-file` flag, if any
+    file_flag: Option<(String, Ident)>,
+
+    /// Type of the flag
+    ty: TokenStream,
+
+    /// True if the flag's value must not be shown
+    secret: bool,
+
+    /// Field the flag was generated from
+    field: Ident,
+
+    /// True if the field is an `Option<T>`
+    is_option: bool,
+
+    /// True if the flag must be present
+    required: bool,
+
+    /// True if the flag must not be given more than once
+    single: bool,
+
+    /// Minimum value of the flag, if present
+    min: Option<Lit>,
+
+    /// Maximum value of the flag, if present
+    max: Option<Lit>,
+
+    /// Minimum number of values in a list flag, if present
+    min_values: Option<usize>,
+
+    /// Maximum number of values in a list flag, if present
+    max_values: Option<usize>,
+
+    /// Value to use in `apply_flags()` if the field is empty
+    default_if_empty: Option<Lit>,
+
+    /// Fields whose flags must be present if this flag is present
+    requires: Vec<Ident>,
+
+    /// Fields whose flags must not be present if this flag is present
+    conflicts_with: Vec<Ident>,
+
+    /// `#[cfg(...)]` attributes on the field
+    cfgs: Vec<Attribute>,
+
+    /// Tokens that define the flag
+    tokens: TokenStream,
+}
+
+/// Returns an expression for the `static` of the flag, or of the first of
+/// its aliases that is present on the command line.
+fn flag_static(flag: &GeneratedFlag) -> TokenStream {
+    let ident = &flag.ident;
+    if flag.aliases.is_empty() {
+        return quote! { #ident };
+    }
+    let aliases = flag.aliases.iter().map(|(_, alias)| alias);
+    quote! {
+        (#( if #aliases.is_present() { &#aliases } else )* { &#ident })
+    }
+}
+
+/// Returns an expression for the value of the flag, from `flag_static()`.
+/// A value that is held by a generated type is taken out of it.
+fn flag_value(flag: &GeneratedFlag) -> TokenStream {
+    let flag_ident = flag_static(flag);
+    if flag.is_parsed {
+        quote! { #flag_ident.flag.0 }
+    } else {
+        quote! { #flag_ident.flag }
+    }
+}
+
+/// Returns a condition that is true if more than one of the flag and its
+/// aliases is present on the command line, and their names to report if it
+/// is, or `None` if the flag has no aliases.
+fn aliases_conflict(flag: &GeneratedFlag) -> Option<(TokenStream, String)> {
+    if flag.aliases.is_empty() {
+        return None;
+    }
+    let ident = &flag.ident;
+    let aliases = flag.aliases.iter().map(|(_, alias)| alias);
+    let names = std::iter::once(&flag.name)
+        .chain(flag.aliases.iter().map(|(name, _)| name))
+        .map(|name| format!("--{}", name))
+        .collect::<Vec<_>>()
+        .join(", ");
+    Some((
+        quote! {
+            [#ident.is_present(), #(#aliases.is_present()),*]
+                .iter()
+                .filter(|present| **present)
+                .count()
+                > 1
+        },
+        names,
+    ))
+}
+
+/// Generate a method that reads every field that has a flag, so that the
+/// fields of a struct that is only used to define flags are not reported as
+/// dead code. `#[allow(dead_code)]` on the method makes it, and the fields
+/// it reads, live.
+fn fields_from_flags(ast: &syn::DeriveInput, flags: &[GeneratedFlag]) -> TokenStream {
+    let ident = &ast.ident;
+    let (impl_generics, ty_generics, where_clause) = ast.generics.split_for_impl();
+    let fields = flags.iter().map(|flag| {
+        let field = &flag.field;
+        let cfgs = &flag.cfgs;
+        quote! {
+            #(#cfgs)*
+            let _ = &self.#field;
+        }
+    });
+
+    quote! {
+        impl #impl_generics #ident #ty_generics #where_clause {
+            #[doc(hidden)]
+            #[allow(dead_code)]
+            fn __gflags_fields(&self) {
+                #(#fields)*
+            }
+        }
+    }
+}
+
+/// Generate a `validate()` method on the struct that checks every flag's
+/// `required`, `single`, `min`, `max`, `min_values`, `max_values`,
+/// `requires`, and `conflicts_with` attributes, and returns all the errors.
+fn validate_from_flags(ast: &syn::DeriveInput, flags: &[GeneratedFlag]) -> TokenStream {
+    let ident = &ast.ident;
+    let (impl_generics, ty_generics, where_clause) = ast.generics.split_for_impl();
+
+    let find_flag = |field: &Ident| -> &GeneratedFlag {
+        match flags.iter().find(|flag| flag.field == *field) {
+            Some(flag) => flag,
+            None => abort!(field, "No flag for field `{}`", field),
+        }
+    };
+
+    let checks = flags.iter().map(|flag| {
+        let name = &flag.name;
+        let flag_ident = flag_static(flag);
+        let mut checks = vec![];
+
+        if let Some((conflict, names)) = aliases_conflict(flag) {
+            checks.push(quote! {
+                if #conflict {
+                    errors.push(format!("only one of {} can be given", #names));
+                }
+            });
+        }
+
+        if flag.required {
+            checks.push(quote! {
+                if !#flag_ident.is_present() {
+                    errors.push(format!("--{} is required", #name));
+                }
+            });
+        }
+
+        // `gflags` keeps the last value of a repeated flag, and counts
+        // how many times it was given
+        if flag.single {
+            checks.push(quote! {
+                if #flag_ident.repeat_count() > 1 {
+                    errors.push(format!("--{} must not be given more than once", #name));
+                }
+            });
+        }
+
+        let value = flag_value(flag);
+        if let Some(min) = &flag.min {
+            checks.push(quote! {
+                if #flag_ident.is_present() && #value < #min {
+                    errors.push(format!("--{} must be at least {}", #name, #min));
+                }
+            });
+        }
+
+        if let Some(max) = &flag.max {
+            checks.push(quote! {
+                if #flag_ident.is_present() && #value > #max {
+                    errors.push(format!("--{} must be at most {}", #name, #max));
+                }
+            });
+        }
+
+        // The value of a list flag is the generated list type, a newtype
+        // around the `Vec<T>`
+        if let Some(min_values) = flag.min_values {
+            checks.push(quote! {
+                if #flag_ident.is_present() && #flag_ident.flag.0.len() < #min_values {
+                    errors.push(format!(
+                        "--{} must have at least {} values, got {}",
+                        #name,
+                        #min_values,
+                        #flag_ident.flag.0.len()
+                    ));
+                }
+            });
+        }
+
+        if let Some(max_values) = flag.max_values {
+            checks.push(quote! {
+                if #flag_ident.is_present() && #flag_ident.flag.0.len() > #max_values {
+                    errors.push(format!(
+                        "--{} must have at most {} values, got {}",
+                        #name,
+                        #max_values,
+                        #flag_ident.flag.0.len()
+                    ));
+                }
+            });
+        }
+
+        for field in &flag.requires {
+            let other = find_flag(field);
+            let other_name = &other.name;
+            let other_ident = flag_static(other);
+            checks.push(quote! {
+                if #flag_ident.is_present() && !#other_ident.is_present() {
+                    errors.push(format!("--{} requires --{}", #name, #other_name));
+                }
+            });
+        }
+
+        for field in &flag.conflicts_with {
+            let other = find_flag(field);
+            let other_name = &other.name;
+            let other_ident = flag_static(other);
+            checks.push(quote! {
+                if #flag_ident.is_present() && #other_ident.is_present() {
+                    errors.push(format!("--{} conflicts with --{}", #name, #other_name));
+                }
+            });
+        }
+
+        let cfgs = &flag.cfgs;
+        quote! {
+            #(#cfgs)*
+            {
+                #(#checks)*
+            }
+        }
+    });
+
+    quote! {
+        impl #impl_generics #ident #ty_generics #where_clause {
+            /// Checks the flags given on the command line, and returns a
+            /// message for every problem found.
+            pub fn validate(&self) -> Result<(), Vec<String>> {
+                let mut errors: Vec<String> = vec![];
+                #(#checks)*
+                if errors.is_empty() {
+                    Ok(())
+                } else {
+                    Err(errors)
+                }
+            }
+        }
+    }
+}
+
+/// Generate an `apply_flags()` method on the struct that copies the value of
+/// every flag that is present on the command line to its field.
+fn apply_from_flags(ast: &syn::DeriveInput, flags: &[GeneratedFlag]) -> TokenStream {
+    let ident = &ast.ident;
+    let (impl_generics, ty_generics, where_clause) = ast.generics.split_for_impl();
+
+    let assignments = flags.iter().map(|flag| {
+        let field = &flag.field;
+        let flag_ident = flag_static(flag);
+        let name = format!("--{}", flag.name);
+        let value = if flag.is_duration {
+            duration_from_str(quote! { #flag_ident.flag }, &name)
+        } else if let Some(parser) = &flag.parse_in_apply {
+            parse_in_apply(parser, quote! { #flag_ident.flag }, &name)
+        } else if flag.is_list {
+            quote! { #flag_ident.flag.0.clone() }
+        } else if flag.is_choice || flag.is_parsed {
+            quote! { #flag_ident.flag.0 }
+        } else {
+            quote! { #flag_ident.flag }
+        };
+        // `Option<T>` fields stay `None` unless the flag is present
+        let value = if flag.is_option {
+            quote! { Some(#value.into()) }
+        } else {
+            quote! { #value.into() }
+        };
+        let file = match &flag.file_flag {
+            Some((file_name, file_ident)) => file_from_flag(flag, file_name, file_ident),
+            None => TokenStream::new(),
+        };
+        let env = match &flag.env {
+            Some(env) => env_from_flag(flag, env),
+            None => TokenStream::new(),
+        };
+        // Replace an empty value (`Some` empty value, for `Option<T>`) with
+        // the default, whether it came from the flag or was already there
+        let default_if_empty = match &flag.default_if_empty {
+            Some(default) if flag.is_option => quote! {
+                if self.#field.as_ref().map_or(false, |value| value.is_empty()) {
+                    self.#field = Some(#default.into());
+                }
+            },
+            Some(default) => quote! {
+                if self.#field.is_empty() {
+                    self.#field = #default.into();
+                }
+            },
+            None => TokenStream::new(),
+        };
+        // The value could come from more than one alias, so exit if it does
+        let conflict = match aliases_conflict(flag) {
+            Some((conflict, names)) => quote! {
+                if #conflict {
+                    eprintln!("Only one of {} can be given", #names);
+                    std::process::exit(1);
+                }
+            },
+            None => TokenStream::new(),
+        };
+        // A field that is gated with `#[cfg(...)]` is only set when it exists
+        let cfgs = &flag.cfgs;
+        quote! {
+            #(#cfgs)*
+            {
+                #conflict
+                if #flag_ident.is_present() {
+                    self.#field = #value;
+                }
+                #file
+                #env
+                #default_if_empty
+            }
+        }
+    });
+
+    let parse_duration = if flags.iter().any(|flag| flag.is_duration) {
+        parse_duration()
+    } else {
+        TokenStream::new()
+    };
+
+    quote! {
+        impl #impl_generics #ident #ty_generics #where_clause {
+            /// Sets each field from its flag, if the flag is present on the
+            /// command line, or from its environment variable, if it has one
+            /// and it is set.
+            #[allow(clippy::useless_conversion)]
+            pub fn apply_flags(&mut self) {
+                #parse_duration
+                #(#assignments)*
+            }
+        }
+    }
+}
+
+/// Returns an `else` branch for `apply_flags()` that sets the field from the
+/// environment variable `env`, if it is set. Reference types (e.g., `&str`,
+/// `&Path`) are borrowed from the variable's value, other types are parsed
+/// with `FromStr`.
+fn env_from_flag(flag: &GeneratedFlag, env: &str) -> TokenStream {
+    let field = &flag.field;
+    let ty = &flag.ty;
+    let parsed = if flag.is_duration {
+        let value = duration_from_str(quote! { &value }, &format!("${}", env));
+        quote! { let value = #value; }
+    } else if let Some(parser) = &flag.parse_in_apply {
+        let value = parse_in_apply(parser, quote! { &value }, &format!("${}", env));
+        quote! { let value = #value; }
+    } else if ty.to_string().starts_with('&') {
+        quote! { let value: #ty = value.as_ref(); }
+    } else {
+        quote! {
+            let value: #ty = match value.parse() {
+                Ok(value) => value,
+                Err(_) => {
+                    eprintln!("Invalid value {:?} for environment variable {}", value, #env);
+                    std::process::exit(1);
+                }
+            };
+        }
+    };
+    let value = if flag.is_list || flag.is_choice || flag.is_parsed {
+        quote! { value.0 }
+    } else {
+        quote! { value }
+    };
+    let value = if flag.is_option {
+        quote! { Some(#value.into()) }
+    } else {
+        quote! { #value.into() }
+    };
+    quote! {
+        else if let Ok(value) = std::env::var(#env) {
+            #parsed
+            self.#field = #value;
+        }
+    }
+}
+
+/// Returns an `else` branch for `apply_flags()` that sets the field from the
+/// contents of the file named by the `--<name>-file` flag, if it is present.
+/// Trailing newlines are not part of the value. The value is converted in
+/// the same way as a value from the environment.
+fn file_from_flag(flag: &GeneratedFlag, file_name: &str, file_ident: &Ident) -> TokenStream {
+    let field = &flag.field;
+    let ty = &flag.ty;
+    let source = format!("--{}", file_name);
+    let parsed = if flag.is_duration {
+        let value = duration_from_str(quote! { value }, &source);
+        quote! { let value = #value; }
+    } else if let Some(parser) = &flag.parse_in_apply {
+        let value = parse_in_apply(parser, quote! { value }, &source);
+        quote! { let value = #value; }
+    } else if ty.to_string().starts_with('&') {
+        quote! { let value: #ty = value.as_ref(); }
+    } else {
+        quote! {
+            let value: #ty = match value.parse() {
+                Ok(value) => value,
+                Err(_) => {
+                    eprintln!("Invalid value {:?} in file {}", value, #file_ident.flag);
+                    std::process::exit(1);
+                }
+            };
+        }
+    };
+    let value = if flag.is_list || flag.is_choice || flag.is_parsed {
+        quote! { value.0 }
+    } else {
+        quote! { value }
+    };
+    let value = if flag.is_option {
+        quote! { Some(#value.into()) }
+    } else {
+        quote! { #value.into() }
+    };
+    quote! {
+        else if #file_ident.is_present() {
+            let contents = match std::fs::read_to_string(#file_ident.flag) {
+                Ok(contents) => contents,
+                Err(e) => {
+                    eprintln!("Can not read {} for {}: {}", #file_ident.flag, #source, e);
+                    std::process::exit(1);
+                }
+            };
+            let value = contents.trim_end_matches(&['\r', '\n'][..]);
+            #parsed
+            self.#field = #value;
+        }
+    }
+}
+
+/// Returns an expression that parses `value`, a `&str`, into a `Duration`
+/// with the function from `parse_duration()`, exiting with an error that
+/// names `source` if it is not valid.
+fn duration_from_str(value: TokenStream, source: &str) -> TokenStream {
+    quote! {
+        match parse_duration(#value) {
+            Ok(value) => value,
+            Err(e) => {
+                eprintln!("Invalid value for {}: {}", #source, e);
+                std::process::exit(1);
+            }
+        }
+    }
+}
+
+/// Returns an expression that parses `value`, a `&str`, with the
+/// `parse_with` function `parser`, exiting with an error that names `source`
+/// if it is not valid.
+fn parse_in_apply(parser: &Path, value: TokenStream, source: &str) -> TokenStream {
+    quote! {
+        match #parser(#value) {
+            Ok(value) => value,
+            Err(e) => {
+                eprintln!("Invalid value {:?} for {}: {}", #value, #source, e);
+                std::process::exit(1);
+            }
+        }
+    }
+}
+
+/// Returns a `parse_duration()` function that parses a duration like `30s`
+/// or `1h30m` into a `Duration`. Each number must be followed by a unit,
+/// one of `ms`, `s`, `m`, `h`, or `d`.
+fn parse_duration() -> TokenStream {
+    quote! {
+        fn parse_duration(value: &str) -> Result<std::time::Duration, String> {
+            let invalid = || format!("invalid duration {:?}", value);
+            let mut total = std::time::Duration::from_secs(0);
+            let mut rest = value.trim();
+            if rest.is_empty() {
+                return Err(invalid());
+            }
+            while !rest.is_empty() {
+                let digits = rest
+                    .find(|c: char| !c.is_ascii_digit())
+                    .unwrap_or(rest.len());
+                let number: u64 = rest[..digits].parse().map_err(|_| invalid())?;
+                rest = &rest[digits..];
+                let unit = rest
+                    .find(|c: char| c.is_ascii_digit())
+                    .unwrap_or(rest.len());
+                let duration = match &rest[..unit] {
+                    "ms" => Some(std::time::Duration::from_millis(number)),
+                    "s" => Some(std::time::Duration::from_secs(number)),
+                    "m" => number.checked_mul(60).map(std::time::Duration::from_secs),
+                    "h" => number.checked_mul(60 * 60).map(std::time::Duration::from_secs),
+                    "d" => number.checked_mul(24 * 60 * 60).map(std::time::Duration::from_secs),
+                    _ => None,
+                };
+                total = duration
+                    .and_then(|duration| total.checked_add(duration))
+                    .ok_or_else(invalid)?;
+                rest = &rest[unit..];
+            }
+            Ok(total)
+        }
+    }
+}
+
+/// Generate a `flags_summary()` function on the struct that describes the
+/// value of every flag.
+fn summary_from_flags(ast: &syn::DeriveInput, flags: &[GeneratedFlag]) -> TokenStream {
+    let ident = &ast.ident;
+    let (impl_generics, ty_generics, where_clause) = ast.generics.split_for_impl();
+
+    let lines = flags.iter().map(|flag| {
+        let name = &flag.name;
+        let value = value_from_flag(flag);
+        let cfgs = &flag.cfgs;
+        quote! {
+            #(#cfgs)*
+            summary.push_str(&format!("--{}: {}\n", #name, #value));
+        }
+    });
+
+    quote! {
+        impl #impl_generics #ident #ty_generics #where_clause {
+            /// Returns a summary of the value of every flag, one per line.
+            pub fn flags_summary() -> String {
+                let mut summary = String::new();
+                #(#lines)*
+                summary
+            }
+        }
+    }
+}
+
+/// Generate a `handle_help()` function on the struct that parses the command
+/// line and prints the help if it was requested.
+fn help_handler(ast: &syn::DeriveInput, gflags: &Path) -> TokenStream {
+    let ident = &ast.ident;
+    let (impl_generics, ty_generics, where_clause) = ast.generics.split_for_impl();
+
+    quote! {
+        impl #impl_generics #ident #ty_generics #where_clause {
+            /// Parses the command line. If `help` was given prints the help
+            /// and exits, otherwise returns the positional arguments.
+            pub fn handle_help() -> Vec<&'static str> {
+                let args = #gflags::parse();
+                if args.contains(&"help") {
+                    #gflags::print_help_and_exit(0);
+                }
+                args
+            }
+        }
+    }
+}
+
+/// Generate a `<STRUCT>_FLAG_TO_FIELD` constant that maps the name of each
+/// flag to the name of the field it was generated from. The constant has
+/// the same visibility as the struct.
+fn field_map_from_flags(ast: &syn::DeriveInput, flags: &[GeneratedFlag]) -> TokenStream {
+    let vis = &ast.vis;
+    let ident = format_ident!(
+        "{}_FLAG_TO_FIELD",
+        screaming_snake_case(&ast.ident.unraw().to_string())
+    );
+    let pairs = flags.iter().map(|flag| {
+        let name = &flag.name;
+        let field = flag.field.unraw().to_string();
+        let cfgs = &flag.cfgs;
+        quote! { #(#cfgs)* (#name, #field) }
+    });
+
+    quote! {
+        #vis const #ident: &[(&str, &str)] = &[ #(#pairs),* ];
+    }
+}
+
+/// Generate a `flag_is_present()` function on the struct that returns
+/// whether the flag for the named field is present on the command line, or
+/// `None` if the field has no flag.
+fn is_present_from_flags(ast: &syn::DeriveInput, flags: &[GeneratedFlag]) -> TokenStream {
+    let ident = &ast.ident;
+    let (impl_generics, ty_generics, where_clause) = ast.generics.split_for_impl();
+
+    let arms = flags.iter().map(|flag| {
+        let field = flag.field.unraw().to_string();
+        let flag_ident = flag_static(flag);
+        let cfgs = &flag.cfgs;
+        quote! {
+            #(#cfgs)*
+            #field => Some(#flag_ident.is_present()),
+        }
+    });
+
+    quote! {
+        impl #impl_generics #ident #ty_generics #where_clause {
+            /// Returns true if the flag for the field `field_name` is present
+            /// on the command line, or `None` if the field has no flag.
+            pub fn flag_is_present(field_name: &str) -> Option<bool> {
+                match field_name {
+                    #(#arms)*
+                    _ => None,
+                }
+            }
+        }
+    }
+}
+
+/// Generate a `<STRUCT>_FLAG_GROUP` constant that pairs `header` with the
+/// names of the flags, for a help renderer to show them under a header. The
+/// constant has the same visibility as the struct.
+fn group_from_flags(ast: &syn::DeriveInput, header: &str, flags: &[GeneratedFlag]) -> TokenStream {
+    let vis = &ast.vis;
+    let ident = format_ident!(
+        "{}_FLAG_GROUP",
+        screaming_snake_case(&ast.ident.unraw().to_string())
+    );
+    let names = flags.iter().map(|flag| {
+        let name = &flag.name;
+        let cfgs = &flag.cfgs;
+        quote! { #(#cfgs)* #name }
+    });
+
+    quote! {
+        #vis const #ident: (&str, &[&str]) = (#header, &[ #(#names),* ]);
+    }
+}
+
+/// Returns `name`, in `CamelCase`, in `SCREAMING_SNAKE_CASE` (e.g.,
+/// `LogConfig` becomes `LOG_CONFIG`).
+fn screaming_snake_case(name: &str) -> String {
+    let mut result = String::new();
+    let mut previous_is_lower = false;
+    for c in name.chars() {
+        if c.is_uppercase() && previous_is_lower {
+            result.push('_');
+        }
+        previous_is_lower = c.is_lowercase() || c.is_ascii_digit();
+        result.extend(c.to_uppercase());
+    }
+    result
+}
+
+/// Generate a `clap_command()` function on the struct that returns a
+/// `clap::Command` with an argument for every flag.
+fn clap_from_flags(ast: &syn::DeriveInput, flags: &[GeneratedFlag]) -> TokenStream {
+    let ident = &ast.ident;
+    let (impl_generics, ty_generics, where_clause) = ast.generics.split_for_impl();
+
+    let args = flags.iter().map(|flag| {
+        let name = &flag.name;
+        let help = &flag.help;
+        let action = if flag.is_bool {
+            quote! { clap::ArgAction::SetTrue }
+        } else {
+            quote! { clap::ArgAction::Set }
+        };
+        let value_name = match &flag.placeholder {
+            Some(placeholder) => quote! { .value_name(#placeholder) },
+            None => TokenStream::new(),
+        };
+        let default_value = match &flag.default_value {
+            Some(default_value) => quote! { .default_value(#default_value) },
+            None => TokenStream::new(),
+        };
+        let cfgs = &flag.cfgs;
+        quote! {
+            #(#cfgs)*
+            let command = command.arg(
+                clap::Arg::new(#name)
+                    .long(#name)
+                    .help(#help)
+                    .action(#action)
+                    #value_name
+                    #default_value
+            );
+        }
+    });
+
+    quote! {
+        impl #impl_generics #ident #ty_generics #where_clause {
+            /// Returns a `clap::Command` with an argument for every flag.
+            pub fn clap_command() -> clap::Command {
+                let command = clap::Command::new(env!("CARGO_PKG_NAME"));
+                #(#args)*
+                command
+            }
+        }
+    }
+}
+
+/// Returns the `effective_flags()` method for the flags.
+fn effective_from_flags(ast: &syn::DeriveInput, flags: &[GeneratedFlag]) -> TokenStream {
+    let ident = &ast.ident;
+    let (impl_generics, ty_generics, where_clause) = ast.generics.split_for_impl();
+
+    let entries = flags.iter().map(|flag| {
+        let name = &flag.name;
+        let flag_ident = flag_static(flag);
+        let value = display_value_from_flag(flag);
+        let cfgs = &flag.cfgs;
+        quote! {
+            #(#cfgs)*
+            (#name.to_string(), #flag_ident.is_present(), #value)
+        }
+    });
+    let display_or_debug = display_or_debug();
+
+    quote! {
+        impl #impl_generics #ident #ty_generics #where_clause {
+            /// Returns the name of every flag, whether it was present on the
+            /// command line, and its value.
+            pub fn effective_flags(&self) -> Vec<(String, bool, String)> {
+                #display_or_debug
+                vec![#(#entries),*]
+            }
+        }
+    }
+}
+
+/// Returns the `non_default_flags()` method for the flags. A flag with a
+/// default differs from it if its value is not equal to the default, a flag
+/// without one if it is present.
+fn diff_from_flags(ast: &syn::DeriveInput, flags: &[GeneratedFlag]) -> TokenStream {
+    let ident = &ast.ident;
+    let (impl_generics, ty_generics, where_clause) = ast.generics.split_for_impl();
+
+    let checks = flags.iter().map(|flag| {
+        let name = &flag.name;
+        let flag_ident = flag_static(flag);
+        let value = flag_value(flag);
+        let differs = match &flag.default {
+            Some(default) => quote! { #value != #default },
+            None => quote! { #flag_ident.is_present() },
+        };
+        let cfgs = &flag.cfgs;
+        quote! {
+            #(#cfgs)*
+            if #differs {
+                names.push(#name.to_string());
+            }
+        }
+    });
+
+    quote! {
+        impl #impl_generics #ident #ty_generics #where_clause {
+            /// Returns the name of every flag whose value differs from its
+            /// default.
+            pub fn non_default_flags(&self) -> Vec<String> {
+                let mut names = vec![];
+                #(#checks)*
+                names
+            }
+        }
+    }
+}
+
+/// Generate a `<STRUCT>FlagsBuilder` type with a setter for every field of
+/// the struct, and a `build()` method that starts from `Default`, sets the
+/// fields that were given, and then calls `apply_flags()`. The builder has
+/// the same visibility as the struct.
+fn builder_from_fields(ast: &syn::DeriveInput, fields: &[&Field]) -> TokenStream {
+    let vis = &ast.vis;
+    let ident = &ast.ident;
+    let builder = format_ident!("{}FlagsBuilder", ident.unraw());
+    let (impl_generics, ty_generics, where_clause) = ast.generics.split_for_impl();
+
+    let cfgs: Vec<Vec<&Attribute>> = fields
+        .iter()
+        .map(|field| {
+            field
+                .attrs
+                .iter()
+                .filter(|attr| attr.path.is_ident("cfg"))
+                .collect()
+        })
+        .collect();
+
+    let members = fields.iter().zip(&cfgs).map(|(field, cfgs)| {
+        let field_ident = field_ident(field);
+        let ty = &field.ty;
+        quote! {
+            #(#cfgs)*
+            #field_ident: Option<#ty>,
+        }
+    });
+
+    let nones = fields.iter().zip(&cfgs).map(|(field, cfgs)| {
+        let field_ident = field_ident(field);
+        quote! {
+            #(#cfgs)*
+            #field_ident: None,
+        }
+    });
+
+    let setters = fields.iter().zip(&cfgs).map(|(field, cfgs)| {
+        let field_ident = field_ident(field);
+        let ty = &field.ty;
+        let doc = format!(
+            " Sets `{}`, unless its flag is present when `build()` is called.",
+            field_ident.unraw()
+        );
+        quote! {
+            #(#cfgs)*
+            #[doc = #doc]
+            pub fn #field_ident(mut self, value: #ty) -> Self {
+                self.#field_ident = Some(value);
+                self
+            }
+        }
+    });
+
+    let assignments = fields.iter().zip(&cfgs).map(|(field, cfgs)| {
+        let field_ident = field_ident(field);
+        quote! {
+            #(#cfgs)*
+            if let Some(value) = self.#field_ident {
+                config.#field_ident = value;
+            }
+        }
+    });
+
+    let doc = format!(
+        " Builds a `{}` from values set in code, overridden by any flags that are present.",
+        ident
+    );
+
+    quote! {
+        #[doc = #doc]
+        #vis struct #builder #impl_generics #where_clause {
+            #(#members)*
+        }
+
+        impl #impl_generics Default for #builder #ty_generics #where_clause {
+            fn default() -> Self {
+                Self {
+                    #(#nones)*
+                }
+            }
+        }
+
+        impl #impl_generics #builder #ty_generics #where_clause {
+            /// Returns a builder with no fields set.
+            pub fn new() -> Self {
+                Self::default()
+            }
+
+            #(#setters)*
+
+            /// Returns the struct, starting from its `Default`, with the
+            /// fields that were set, and then the flags applied with
+            /// `apply_flags()`.
+            pub fn build(self) -> #ident #ty_generics
+            where
+                #ident #ty_generics: Default,
+            {
+                let mut config = #ident::default();
+                #(#assignments)*
+                config.apply_flags();
+                config
+            }
+        }
+    }
+}
+
+/// Returns the `flag_value_strings()` method for the flags.
+fn value_map_from_flags(ast: &syn::DeriveInput, flags: &[GeneratedFlag]) -> TokenStream {
+    let ident = &ast.ident;
+    let (impl_generics, ty_generics, where_clause) = ast.generics.split_for_impl();
+
+    let entries = flags.iter().map(|flag| {
+        let name = &flag.name;
+        let value = display_value_from_flag(flag);
+        let cfgs = &flag.cfgs;
+        quote! {
+            #(#cfgs)*
+            map.insert(#name.to_string(), #value);
+        }
+    });
+    let display_or_debug = display_or_debug();
+
+    quote! {
+        impl #impl_generics #ident #ty_generics #where_clause {
+            /// Returns the value of every flag, keyed by the flag's name.
+            pub fn flag_value_strings(&self) -> std::collections::BTreeMap<String, String> {
+                #display_or_debug
+                let mut map = std::collections::BTreeMap::new();
+                #(#entries)*
+                map
+            }
+        }
+    }
+}
+
+/// Returns the items that `display_value_from_flag()` uses to format a value.
+///
+/// Values are formatted with `Display` if the flag's type implements it,
+/// and `Debug` otherwise. This is chosen at compile time with autoref
+/// specialization: method lookup on `&&Formatter` finds the `Display` impl
+/// on `&Formatter` first, and only if that does not apply derefs to the
+/// `Debug` impl on `Formatter`.
+fn display_or_debug() -> TokenStream {
+    quote! {
+        struct Formatter<'a, T>(&'a T);
+
+        trait ViaDisplay {
+            fn format_value(&self) -> String;
+        }
+
+        impl<T: std::fmt::Display> ViaDisplay for &Formatter<'_, T> {
+            fn format_value(&self) -> String {
+                self.0.to_string()
+            }
+        }
+
+        trait ViaDebug {
+            fn format_value(&self) -> String;
+        }
+
+        impl<T: std::fmt::Debug> ViaDebug for Formatter<'_, T> {
+            fn format_value(&self) -> String {
+                format!("{:?}", self.0)
+            }
+        }
+    }
+}
+
+/// Returns an expression that formats the flag's current value as a
+/// `String` with `Display`, or `Debug` if its type does not implement
+/// `Display`, redacting secrets. Uses the items from `display_or_debug()`.
+fn display_value_from_flag(flag: &GeneratedFlag) -> TokenStream {
+    let flag_ident = flag_static(flag);
+    let has_default = flag.has_default;
+    let value = if flag.secret {
+        quote! { "***".to_string() }
+    } else {
+        let value = flag_value(flag);
+        quote! { (&&Formatter(&#value)).format_value() }
+    };
+    quote! {
+        if #flag_ident.is_present() || #has_default {
+            #value
+        } else {
+            "<unset>".to_string()
+        }
+    }
+}
+
+/// Returns an expression that formats the flag's current value as a
+/// `String`, redacting secrets.
+fn value_from_flag(flag: &GeneratedFlag) -> TokenStream {
+    let flag_ident = flag_static(flag);
+    let has_default = flag.has_default;
+    let value = if flag.secret {
+        quote! { "***".to_string() }
+    } else {
+        let value = flag_value(flag);
+        quote! { format!("{:?}", #value) }
+    };
+    quote! {
+        if #flag_ident.is_present() || #has_default {
+            #value
+        } else {
+            "<unset>".to_string()
+        }
+    }
+}
+
+/// Represents a `#[gflags(...)]` attribute on a struct or field.
+#[derive(Debug, Default)]
+struct GFlagsAttribute {
+    /// Some(true) if this field should be skipped (do not generate a flag
+    /// for it), Some(false) if it should not be skipped even if
+    /// `skip_all` or `only_pub` would skip it
+    skip: Option<bool>,
+
+    /// True if every field should be skipped unless it has `skip = false`
+    skip_all: bool,
+
+    /// True if each flag's help should name the field it was generated from
+    annotate_source: bool,
+
+    /// True if an `apply_flags()` method should be generated
+    generate_apply: bool,
+
+    /// True if a `validate()` method should be generated
+    generate_validate: bool,
+
+    /// True if the prefix should be removed from the start of field names
+    dedup_prefix: bool,
+
+    /// True if fields with `#[serde(flatten)]` should be skipped
+    respect_serde_flatten: bool,
+
+    /// Prefix to apply to this flag (or global)
+    prefix: Option<String>,
+
+    /// Name to use instead of the field's name, from `name` or `rename`
+    name: Option<LitStr>,
+
+    /// Keyword that set `name`, either `name` or `rename`
+    name_keyword: Option<&'static str>,
+
+    /// Namespace to apply to flag names, before the prefix
+    namespace: Option<String>,
+
+    /// Column to wrap help text at, if any
+    wrap: Option<usize>,
+
+    /// Number of spaces to indent the second and later lines of help text
+    help_indent: Option<usize>,
+
+    /// Prefix to apply to environment variable names, if any
+    env_prefix: Option<String>,
+
+    /// Header to group the flags under in help, if any
+    group_header: Option<String>,
+
+    /// True if every flag should be read from an environment variable
+    auto_env: bool,
+
+    /// Environment variable to read this flag from, if any
+    env: Option<String>,
+
+    /// Path to the `gflags` crate in generated code
+    crate_path: Option<Path>,
+
+    /// Casing for this flag
+    flag_case: Option<FlagCase>,
+
+    /// Casing for this flag, set explicitly with `separator`. Takes
+    /// precedence over any casing implied by `prefix`
+    separator: Option<FlagCase>,
+
+    /// Casing for the separator after the namespace and the prefix, set
+    /// with `prefix_separator`. Takes precedence over `separator`
+    prefix_separator: Option<FlagCase>,
+
+    /// Casing for the separator between the words of the field name, set
+    /// with `word_separator`. Takes precedence over `separator`
+    word_separator: Option<FlagCase>,
+
+    /// Letter case to apply to this flag's name
+    letter_case: Option<LetterCase>,
+
+    /// Tokens that define the type to use for this flag
+    ty: Option<TokenStream>,
+
+    /// Visibility for the flag
+    visibility: Option<TokenStream>,
+
+    /// Placeholder to display in the help
+    placeholder: Option<LitStr>,
+
+    /// True if placeholders should include the flag's default value
+    placeholder_shows_default: bool,
+
+    /// True if flags without a placeholder should get one from their name
+    placeholder_from_name: bool,
+
+    /// Feature that must be enabled for anything to be generated
+    feature: Option<String>,
+
+    /// True if nothing should be generated outside `#[cfg(test)]`
+    test_only: bool,
+
+    /// Extra text to append to the help
+    help_suffix: Option<String>,
+
+    /// File to read extra help from, relative to `CARGO_MANIFEST_DIR`
+    help_file: Option<LitStr>,
+
+    /// Default value if the flag is not set
+    default: Option<TokenStream>,
+
+    /// Expression to compute the default value if the flag is not set
+    default_expr: Option<Expr>,
+
+    /// Function to parse the flag's value with
+    parse_with: Option<Path>,
+
+    /// True if the flag's value should be parsed with `FromStr`
+    from_str: bool,
+
+    /// Delimiter to split a `Vec<T>` flag's value on
+    delimiter: Option<String>,
+
+    /// Values the flag may have, if it is restricted
+    choices: Vec<String>,
+
+    /// True if every field must have a doc comment
+    require_docs: bool,
+
+    /// True if unknown keywords are warned about instead of rejected
+    lenient: bool,
+
+    /// Keywords that were not recognised. They are reported once it is known
+    /// whether `lenient` is in effect
+    unknown: Vec<Path>,
+
+    /// Span of each keyword that was given, so errors about a keyword can
+    /// point at it
+    spans: HashMap<String, Span>,
+
+    /// True if help text constants should be generated
+    export_help: bool,
+
+    /// True if a flag name to field name constant should be generated
+    export_field_map: bool,
+
+    /// Paths of wrapper types to unwrap like `Option<T>`
+    unwrap: Vec<String>,
+
+    /// Transforms to apply to field names, in order
+    transforms: Vec<Transform>,
+
+    /// Acronyms to keep as single words when splitting names into words
+    acronyms: Vec<String>,
+
+    /// Default values for fields, keyed by field name
+    defaults: Vec<(Ident, TokenStream)>,
+
+    /// True if an abbreviated flag should also be generated
+    abbrev: bool,
+
+    /// True if a flag with the other separator should also be generated
+    both_cases: bool,
+
+    /// True if a `flags_summary()` function should be generated
+    generate_summary: bool,
+
+    /// True if a `handle_help()` function should be generated
+    generate_help_handler: bool,
+
+    /// True if a `clap_command()` function should be generated
+    generate_clap: bool,
+
+    /// True if an `effective_flags()` method should be generated
+    generate_effective: bool,
+
+    /// True if a `non_default_flags()` method should be generated
+    generate_diff: bool,
+
+    /// True if a `<STRUCT>FlagsBuilder` type should be generated
+    generate_builder: bool,
+
+    /// True if a `flag_value_strings()` method should be generated
+    generate_value_map: bool,
+
+    /// True if a `flag_is_present()` function should be generated
+    generate_is_present: bool,
+
+    /// True if flags should only be generated for public fields
+    only_pub: bool,
+
+    /// True if the flag's value is secret
+    secret: bool,
+
+    /// True if the flag should have a companion `--<name>-file` flag
+    from_file: bool,
+
+    /// True if the help should describe disabling the flag
+    invert_help: bool,
+
+    /// True if the field is an `Option<T>`, even if its type does not look
+    /// like one
+    optional: bool,
+
+    /// True if the flag must be present
+    required: bool,
+
+    /// True if the flag must not be given more than once
+    single: bool,
+
+    /// Minimum value of the flag, if present
+    min: Option<Lit>,
+
+    /// Maximum value of the flag, if present
+    max: Option<Lit>,
+
+    /// Minimum number of values in a list flag, if present
+    min_values: Option<usize>,
+
+    /// Maximum number of values in a list flag, if present
+    max_values: Option<usize>,
+
+    /// Value to use in `apply_flags()` if the field is empty
+    default_if_empty: Option<Lit>,
+
+    /// Fields whose flags must be present if this flag is present
+    requires: Vec<Ident>,
+
+    /// Fields whose flags must not be present if this flag is present
+    conflicts_with: Vec<Ident>,
+
+    /// Extra attributes for the flag's `static`
+    attrs: Vec<Attribute>,
+}
+
+/// Keywords that need to be recognised before parsing `NestedMeta`
+mod kw {
+    syn::custom_keyword!(default);
+}
+
+/// A single `key = value` (or `key`) argument in a `#[gflags(...)]` attribute.
+enum GFlagsArg {
+    /// Any argument that is valid `NestedMeta`
+    Meta(NestedMeta),
+
+    /// `default = ...`, where the value may be any expression, not just a
+    /// literal, so it cannot be parsed as `NestedMeta`
+    Default(kw::default, Box<Expr>),
+}
+
+impl Parse for GFlagsArg {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        if input.peek(kw::default) && input.peek2(Token![=]) {
+            let keyword = input.parse::<kw::default>()?;
+            input.parse::<Token![=]>()?;
+            Ok(GFlagsArg::Default(keyword, Box::new(input.parse()?)))
+        } else {
+            Ok(GFlagsArg::Meta(input.parse()?))
+        }
+    }
+}
+
+impl From<&Attribute> for GFlagsAttribute {
+    fn from(attr: &Attribute) -> Self {
+        // `parse_meta()` fails if a `default` is not a literal, so it is only
+        // used to check the overall shape of the attribute
+        match attr.parse_meta() {
+            Ok(Meta::List(meta)) if meta.nested.is_empty() => {
+                abort!(meta, "`#[gflags(...)]` expects a non-empty parameter list")
+            }
+            Ok(Meta::List(_)) | Err(_) => (),
+            Ok(meta) => abort!(meta, "`#[gflags(...)]` expects a parameter list"),
+        }
+
+        let args = match attr.parse_args_with(Punctuated::<GFlagsArg, Token![,]>::parse_terminated)
+        {
+            Ok(args) => args,
+            Err(e) => abort!(e.span(), e),
+        };
+
+        let mut config = GFlagsAttribute::default();
+
+        let keywords: HashSet<&'static str> = [
+            "abbrev",
+            "both_cases",
+            "acronyms",
+            "annotate_source",
+            "attr",
+            "conflicts_with",
+            "dedup_prefix",
+            "default",
+            "default_expr",
+            "defaults",
+            "export_field_map",
+            "export_help",
+            "auto_env",
+            "crate_path",
+            "env",
+            "env_prefix",
+            "feature",
+            "generate_apply",
+            "generate_validate",
+            "generate_effective",
+            "generate_diff",
+            "generate_builder",
+            "generate_value_map",
+            "generate_is_present",
+            "default_from_default",
+            "generate_clap",
+            "generate_help_handler",
+            "generate_summary",
+            "group_header",
+            "help_file",
+            "help_indent",
+            "help_suffix",
+            "invert_help",
+            "lowercase",
+            "max",
+            "default_if_empty",
+            "delimiter",
+            "choices",
+            "min",
+            "min_values",
+            "max_values",
+            "name",
+            "namespace",
+            "only_pub",
+            "optional",
+            "parse_with",
+            "from_str",
+            "from_file",
+            "placeholder",
+            "placeholder_shows_default",
+            "placeholder_from_name",
+            "prefix",
+            "prefix_separator",
+            "require_docs",
+            "required",
+            "lenient",
+            "single",
+            "rename",
+            "requires",
+            "respect_serde_flatten",
+            "secret",
+            "separator",
+            "skip",
+            "skip_all",
+            "test_only",
+            "type",
+            "transform",
+            "unwrap",
+            "uppercase",
+            "visibility",
+            "word_separator",
+            "wrap",
+        ]
+        .iter()
+        .cloned()
+        .collect();
+
+        for arg in args {
+            let kv = match arg {
+                GFlagsArg::Default(keyword, expr) => {
+                    config.spans.insert("default".to_string(), keyword.span);
+                    config.default = Some(default_from_expr(*expr));
+                    continue;
+                }
+                GFlagsArg::Meta(kv) => kv,
+            };
+
+            let kv = match kv {
+                NestedMeta::Meta(Meta::Path(path)) => {
+                    let known = match path.get_ident() {
+                        Some(keyword) => keywords.contains(&keyword.to_string().as_ref()),
+                        None => false,
+                    };
+                    if !known {
+                        config.unknown.push(path);
+                        continue;
+                    }
+                    config.spans.insert(path_to_string(&path), path.span());
+
+                    if path.is_ident("skip") {
+                        config.skip = Some(true);
+                        continue;
+                    }
+
+                    if path.is_ident("require_docs") {
+                        config.require_docs = true;
+                        continue;
+                    }
+
+                    if path.is_ident("lenient") {
+                        config.lenient = true;
+                        continue;
+                    }
+
+                    if path.is_ident("abbrev") {
+                        config.abbrev = true;
+                        continue;
+                    }
+
+                    if path.is_ident("both_cases") {
+                        config.both_cases = true;
+                        continue;
+                    }
+
+                    if path.is_ident("annotate_source") {
+                        config.annotate_source = true;
+                        continue;
+                    }
+
+                    if path.is_ident("generate_summary") {
+                        config.generate_summary = true;
+                        continue;
+                    }
+
+                    if path.is_ident("generate_help_handler") {
+                        config.generate_help_handler = true;
+                        continue;
+                    }
+
+                    if path.is_ident("auto_env") {
+                        config.auto_env = true;
+                        continue;
+                    }
+
+                    if path.is_ident("generate_clap") {
+                        if !cfg!(feature = "clap") {
+                            abort!(
+                                path,
+                                "`#[gflags(generate_clap)]` requires the `clap` feature of `gflags-derive`"
+                            );
+                        }
+                        config.generate_clap = true;
+                        continue;
+                    }
+
+                    if path.is_ident("dedup_prefix") {
+                        config.dedup_prefix = true;
+                        continue;
+                    }
+
+                    if path.is_ident("respect_serde_flatten") {
+                        config.respect_serde_flatten = true;
+                        continue;
+                    }
+
+                    if path.is_ident("placeholder_shows_default") {
+                        config.placeholder_shows_default = true;
+                        continue;
+                    }
+
+                    if path.is_ident("placeholder_from_name") {
+                        config.placeholder_from_name = true;
+                        continue;
+                    }
+
+                    if path.is_ident("test_only") {
+                        config.test_only = true;
+                        continue;
+                    }
+
+                    if path.is_ident("generate_apply") {
+                        config.generate_apply = true;
+                        continue;
+                    }
+
+                    if path.is_ident("generate_validate") {
+                        config.generate_validate = true;
+                        continue;
+                    }
+
+                    if path.is_ident("optional") {
+                        config.optional = true;
+                        continue;
+                    }
+
+                    if path.is_ident("from_file") {
+                        config.from_file = true;
+                        continue;
+                    }
+
+                    if path.is_ident("from_str") {
+                        config.from_str = true;
+                        continue;
+                    }
+
+                    if path.is_ident("required") {
+                        config.required = true;
+                        continue;
+                    }
+
+                    if path.is_ident("single") {
+                        config.single = true;
+                        continue;
+                    }
+
+                    if path.is_ident("generate_effective") {
+                        config.generate_effective = true;
+                        continue;
+                    }
+
+                    if path.is_ident("generate_diff") {
+                        config.generate_diff = true;
+                        continue;
+                    }
+
+                    if path.is_ident("generate_builder") {
+                        config.generate_builder = true;
+                        continue;
+                    }
+
+                    if path.is_ident("generate_value_map") {
+                        config.generate_value_map = true;
+                        continue;
+                    }
+
+                    if path.is_ident("generate_is_present") {
+                        config.generate_is_present = true;
+                        continue;
+                    }
+
+                    if path.is_ident("default_from_default") {
+                        abort!(
+                            path,
+                            "`#[gflags(default_from_default)]` is not supported";
+                            note = "Flag defaults must be constants, and `Default::default()` is not a `const fn`";
+                            help = "Define a `const` for the value and use it in both `Default` and `#[gflags(default = ...)]`"
+                        );
+                    }
+
+                    if path.is_ident("only_pub") {
+                        config.only_pub = true;
+                        continue;
+                    }
+
+                    if path.is_ident("skip_all") {
+                        config.skip_all = true;
+                        continue;
+                    }
+
+                    if path.is_ident("secret") {
+                        config.secret = true;
+                        continue;
+                    }
+
+                    if path.is_ident("invert_help") {
+                        config.invert_help = true;
+                        continue;
+                    }
+
+                    if path.is_ident("export_help") {
+                        config.export_help = true;
+                        continue;
+                    }
+
+                    if path.is_ident("export_field_map") {
+                        config.export_field_map = true;
+                        continue;
+                    }
+
+                    if path.is_ident("lowercase") {
+                        config.letter_case = Some(LetterCase::Lowercase);
+                        continue;
+                    }
+
+                    if path.is_ident("uppercase") {
+                        config.letter_case = Some(LetterCase::Uppercase);
+                        continue;
+                    }
+
+                    abort!(path, "Keyword `{}` requires a value", path_to_string(&path));
+                }
+                NestedMeta::Meta(Meta::List(list)) if list.path.is_ident("defaults") => {
+                    for default in list.nested {
+                        match default {
+                            NestedMeta::Meta(Meta::NameValue(kv)) => match kv.path.get_ident() {
+                                Some(ident) => config
+                                    .defaults
+                                    .push((ident.clone(), default_from_lit(kv.lit))),
+                                None => abort!(
+                                    kv.path,
+                                    "`#[gflags(defaults(...))]` expects field names"
+                                ),
+                            },
+                            _ => abort!(
+                                default,
+                                "`#[gflags(defaults(...))]` expects field=value pairs"
+                            ),
+                        }
+                    }
+                    continue;
+                }
+                NestedMeta::Meta(Meta::NameValue(kv)) => kv,
+                _ => abort!(kv, "`#[gflags(...)]` expects key=value pairs"),
+            };
+            config
+                .spans
+                .insert(path_to_string(&kv.path), kv.path.span());
+
+            if kv.path.is_ident("abbrev") {
+                abort!(kv.lit, "`#[gflags(abbrev)]` does not take a value");
+            }
+
+            if kv.path.is_ident("both_cases") {
+                abort!(kv.lit, "`#[gflags(both_cases)]` does not take a value");
+            }
+
+            if kv.path.is_ident("default_expr") {
+                config.default_expr = match kv.lit {
+                    Lit::Str(lit) => match lit.parse::<Expr>() {
+                        Ok(expr) => Some(expr),
+                        Err(e) => abort!(
+                            lit,
+                            "`#[gflags(default_expr=...)]` expects a valid expression: {}",
+                            e
+                        ),
+                    },
+                    _ => abort!(
+                        kv.lit,
+                        "`#[gflags(default_expr=...)]` expects a quoted string"
+                    ),
+                };
+                continue;
+            }
+
+            if kv.path.is_ident("defaults") {
+                abort!(
+                    kv.lit,
+                    "`#[gflags(defaults(...))]` expects a list of field=value pairs"
+                );
+            }
+
+            if kv.path.is_ident("crate_path") {
+                config.crate_path = match kv.lit {
+                    Lit::Str(lit) => match lit.parse::<Path>() {
+                        Ok(path) => Some(path),
+                        Err(_) => abort!(
+                            lit,
+                            "`#[gflags(crate_path=...)]` expects the path to a crate"
+                        ),
+                    },
+                    _ => abort!(
+                        kv.lit,
+                        "`#[gflags(crate_path=...)]` expects a quoted string"
+                    ),
+                };
+                continue;
+            }
+
+            if kv.path.is_ident("parse_with") {
+                config.parse_with = match kv.lit {
+                    Lit::Str(lit) => match lit.parse::<Path>() {
+                        Ok(path) => Some(path),
+                        Err(_) => abort!(
+                            lit,
+                            "`#[gflags(parse_with=...)]` expects the path to a function"
+                        ),
+                    },
+                    _ => abort!(
+                        kv.lit,
+                        "`#[gflags(parse_with=...)]` expects a quoted string"
+                    ),
+                };
+                continue;
+            }
+
+            if kv.path.is_ident("help_file") {
+                config.help_file = match kv.lit {
+                    Lit::Str(lit) => {
+                        if lit.value().is_empty() {
+                            abort!(
+                                lit,
+                                "`#[gflags(help_file=...)]` expects a non-empty quoted string"
+                            )
+                        }
+                        Some(lit)
+                    }
+                    _ => abort!(kv.lit, "`#[gflags(help_file=...)]` expects a quoted string"),
+                };
+                continue;
+            }
+
+            if kv.path.is_ident("choices") {
+                match &kv.lit {
+                    Lit::Str(lit) => {
+                        for choice in lit.value().split(',').map(str::trim) {
+                            if choice.is_empty() {
+                                abort!(
+                                    lit,
+                                    "`#[gflags(choices=...)]` expects a comma separated list of values"
+                                );
+                            }
+                            if config.choices.iter().any(|c| c == choice) {
+                                abort!(lit, "Choice `{}` is given more than once", choice);
+                            }
+                            config.choices.push(choice.to_string());
+                        }
+                    }
+                    _ => abort!(kv.lit, "`#[gflags(choices=...)]` expects a quoted string"),
+                };
+                continue;
+            }
+
+            if kv.path.is_ident("delimiter") {
+                config.delimiter = match kv.lit {
+                    Lit::Str(lit) => {
+                        if lit.value().is_empty() {
+                            abort!(
+                                lit,
+                                "`#[gflags(delimiter=...)]` expects a non-empty quoted string"
+                            )
+                        }
+                        Some(lit.value())
+                    }
+                    _ => abort!(kv.lit, "`#[gflags(delimiter=...)]` expects a quoted string"),
+                };
+                continue;
+            }
+
+            if kv.path.is_ident("name") || kv.path.is_ident("rename") {
+                let keyword = if kv.path.is_ident("name") {
+                    "name"
+                } else {
+                    "rename"
+                };
+                if matches!(config.name_keyword, Some(other) if other != keyword) {
+                    abort!(
+                        kv.path,
+                        "Use `#[gflags(name=...)]` or `#[gflags(rename=...)]`, not both"
+                    );
+                }
+                config.name = match kv.lit {
+                    Lit::Str(lit) if !lit.value().is_empty() => Some(lit),
+                    lit => abort!(
+                        lit,
+                        "`#[gflags({}=...)]` expects a non-empty quoted string",
+                        keyword
+                    ),
+                };
+                config.name_keyword = Some(keyword);
+                continue;
+            }
+
+            if kv.path.is_ident("help_suffix") {
+                config.help_suffix = match kv.lit {
+                    Lit::Str(lit) => {
+                        if lit.value().is_empty() {
+                            abort!(
+                                lit,
+                                "`#[gflags(help_suffix=...)]` expects a non-empty quoted string"
+                            )
+                        }
+                        Some(lit.value())
+                    }
+                    _ => abort!(
+                        kv.lit,
+                        "`#[gflags(help_suffix=...)]` expects a quoted string"
+                    ),
+                };
+                continue;
+            }
+
+            if kv.path.is_ident("placeholder_from_name") {
+                abort!(
+                    kv.lit,
+                    "`#[gflags(placeholder_from_name)]` does not take a value"
+                );
+            }
+
+            if kv.path.is_ident("test_only") {
+                abort!(kv.lit, "`#[gflags(test_only)]` does not take a value");
+            }
+
+            if kv.path.is_ident("placeholder_shows_default") {
+                abort!(
+                    kv.lit,
+                    "`#[gflags(placeholder_shows_default)]` does not take a value"
+                );
+            }
+
+            if kv.path.is_ident("placeholder") {
+                config.placeholder = match kv.lit {
+                    Lit::Str(lit) => {
+                        if lit.value().is_empty() {
+                            abort!(
+                                lit,
+                                "`#[gflags(placeholder=...)]` expects a non-empty quoted string"
+                            )
+                        }
+                        Some(lit)
+                    }
+                    _ => abort!(
+                        kv.lit,
+                        "`#[gflags(placeholder=...)]` expects a quoted string"
+                    ),
+                };
+                continue;
+            }
+
+            if kv.path.is_ident("namespace") {
+                config.namespace = match kv.lit {
+                    Lit::Str(lit) => {
+                        if lit.value().is_empty() {
+                            abort!(
+                                lit,
+                                "`#[gflags(namespace=...)]` expects a non-empty quoted string"
+                            );
+                        }
+                        Some(lit.value())
+                    }
+                    _ => abort!(kv.lit, "`#[gflags(namespace=...)]` expects a quoted string"),
+                };
+                continue;
+            }
+
+            if kv.path.is_ident("prefix") {
+                let mut prefix = match kv.lit {
+                    Lit::Str(lit) => {
+                        if lit.value().is_empty() {
+                            abort!(
+                                lit,
+                                "`#[gflags(prefix=...)]` expects a non-empty quoted string"
+                            );
+                        }
+
+                        lit.value()
+                    }
+                    _ => abort!(kv.lit, "`#[gflags(prefix=...)]` expects a quoted string"),
+                };
+
+                if prefix.ends_with('_') {
+                    config.flag_case = Some(SnakeCase);
+                    prefix.pop();
+                }
+
+                if prefix.ends_with('-') {
+                    config.flag_case = Some(KebabCase);
+                    prefix.pop();
+                }
+
+                config.prefix = Some(prefix);
+                continue;
+            }
+
+            if kv.path.is_ident("separator") {
+                config.separator = Some(separator_from_lit("separator", kv.lit));
+                continue;
+            }
+
+            if kv.path.is_ident("prefix_separator") {
+                config.prefix_separator = Some(separator_from_lit("prefix_separator", kv.lit));
+                continue;
+            }
+
+            if kv.path.is_ident("word_separator") {
+                config.word_separator = Some(separator_from_lit("word_separator", kv.lit));
+                continue;
+            }
+
+            if kv.path.is_ident("annotate_source") {
+                abort!(kv.lit, "`#[gflags(annotate_source)]` does not take a value");
+            }
+
+            if kv.path.is_ident("feature") {
+                config.feature = match kv.lit {
+                    Lit::Str(lit) => {
+                        if lit.value().is_empty() {
+                            abort!(
+                                lit,
+                                "`#[gflags(feature=...)]` expects a non-empty quoted string"
+                            );
+                        }
+                        Some(lit.value())
+                    }
+                    _ => abort!(kv.lit, "`#[gflags(feature=...)]` expects a quoted string"),
+                };
+                continue;
+            }
+
+            if kv.path.is_ident("env") {
+                config.env = match kv.lit {
+                    Lit::Str(lit) => {
+                        if lit.value().is_empty() {
+                            abort!(
+                                lit,
+                                "`#[gflags(env=...)]` expects a non-empty quoted string"
+                            );
+                        }
+                        Some(lit.value())
+                    }
+                    _ => abort!(kv.lit, "`#[gflags(env=...)]` expects a quoted string"),
+                };
+                continue;
+            }
+
+            if kv.path.is_ident("group_header") {
+                config.group_header = match kv.lit {
+                    Lit::Str(lit) => {
+                        if lit.value().is_empty() {
+                            abort!(
+                                lit,
+                                "`#[gflags(group_header=...)]` expects a non-empty quoted string"
+                            );
+                        }
+                        Some(lit.value())
+                    }
+                    _ => abort!(
+                        kv.lit,
+                        "`#[gflags(group_header=...)]` expects a quoted string"
+                    ),
+                };
+                continue;
+            }
+
+            if kv.path.is_ident("env_prefix") {
+                config.env_prefix = match kv.lit {
+                    Lit::Str(lit) => {
+                        if lit.value().is_empty() {
+                            abort!(
+                                lit,
+                                "`#[gflags(env_prefix=...)]` expects a non-empty quoted string"
+                            );
+                        }
+                        Some(lit.value())
+                    }
+                    _ => abort!(
+                        kv.lit,
+                        "`#[gflags(env_prefix=...)]` expects a quoted string"
+                    ),
+                };
+                continue;
+            }
+
+            if kv.path.is_ident("auto_env") {
+                abort!(kv.lit, "`#[gflags(auto_env)]` does not take a value");
+            }
+
+            if kv.path.is_ident("export_help") {
+                abort!(kv.lit, "`#[gflags(export_help)]` does not take a value");
+            }
+
+            if kv.path.is_ident("export_field_map") {
+                abort!(
+                    kv.lit,
+                    "`#[gflags(export_field_map)]` does not take a value"
+                );
+            }
+
+            if kv.path.is_ident("dedup_prefix") {
+                abort!(kv.lit, "`#[gflags(dedup_prefix)]` does not take a value");
+            }
+
+            if kv.path.is_ident("generate_apply") {
+                abort!(kv.lit, "`#[gflags(generate_apply)]` does not take a value");
+            }
+
+            if kv.path.is_ident("generate_validate") {
+                abort!(
+                    kv.lit,
+                    "`#[gflags(generate_validate)]` does not take a value"
+                );
+            }
+
+            if kv.path.is_ident("default_if_empty") {
+                config.default_if_empty = match kv.lit {
+                    Lit::Str(lit) => Some(Lit::Str(lit)),
+                    _ => abort!(
+                        kv.lit,
+                        "`#[gflags(default_if_empty=...)]` expects a quoted string"
+                    ),
+                };
+                continue;
+            }
+
+            if kv.path.is_ident("max") {
+                config.max = match kv.lit {
+                    lit @ Lit::Int(_) | lit @ Lit::Float(_) => Some(lit),
+                    _ => abort!(kv.lit, "`#[gflags(max=...)]` expects a number"),
+                };
+                continue;
+            }
+
+            if kv.path.is_ident("min") {
+                config.min = match kv.lit {
+                    lit @ Lit::Int(_) | lit @ Lit::Float(_) => Some(lit),
+                    _ => abort!(kv.lit, "`#[gflags(min=...)]` expects a number"),
+                };
+                continue;
+            }
+
+            if kv.path.is_ident("min_values") {
+                config.min_values = match &kv.lit {
+                    Lit::Int(lit) => match lit.base10_parse::<usize>() {
+                        Ok(n) => Some(n),
+                        _ => abort!(lit, "`#[gflags(min_values=...)]` expects an integer"),
+                    },
+                    _ => abort!(kv.lit, "`#[gflags(min_values=...)]` expects an integer"),
+                };
+                continue;
+            }
+
+            if kv.path.is_ident("max_values") {
+                config.max_values = match &kv.lit {
+                    Lit::Int(lit) => match lit.base10_parse::<usize>() {
+                        Ok(n) => Some(n),
+                        _ => abort!(lit, "`#[gflags(max_values=...)]` expects an integer"),
+                    },
+                    _ => abort!(kv.lit, "`#[gflags(max_values=...)]` expects an integer"),
+                };
+                continue;
+            }
+
+            if kv.path.is_ident("optional") {
+                abort!(kv.lit, "`#[gflags(optional)]` does not take a value");
+            }
+
+            if kv.path.is_ident("from_file") {
+                abort!(kv.lit, "`#[gflags(from_file)]` does not take a value");
+            }
+
+            if kv.path.is_ident("from_str") {
+                abort!(kv.lit, "`#[gflags(from_str)]` does not take a value");
+            }
+
+            if kv.path.is_ident("required") {
+                abort!(kv.lit, "`#[gflags(required)]` does not take a value");
+            }
+
+            if kv.path.is_ident("single") {
+                abort!(kv.lit, "`#[gflags(single)]` does not take a value");
+            }
+
+            if kv.path.is_ident("conflicts_with") {
+                match kv.lit {
+                    Lit::Str(lit) => match lit.parse::<Ident>() {
+                        Ok(ident) => config.conflicts_with.push(ident),
+                        Err(_) => {
+                            abort!(lit, "`#[gflags(conflicts_with=...)]` expects a field name")
+                        }
+                    },
+                    _ => abort!(
+                        kv.lit,
+                        "`#[gflags(conflicts_with=...)]` expects a quoted string"
+                    ),
+                };
+                continue;
+            }
+
+            if kv.path.is_ident("attr") {
+                match kv.lit {
+                    Lit::Str(lit) => match Attribute::parse_outer.parse_str(&lit.value()) {
+                        Ok(attrs) if !attrs.is_empty() => config.attrs.extend(attrs),
+                        _ => abort!(
+                            lit,
+                            "`#[gflags(attr=...)]` expects one or more attributes, e.g., \"#[doc(hidden)]\""
+                        ),
+                    },
+                    _ => abort!(kv.lit, "`#[gflags(attr=...)]` expects a quoted string"),
+                };
+                continue;
+            }
+
+            if kv.path.is_ident("requires") {
+                match kv.lit {
+                    Lit::Str(lit) => match lit.parse::<Ident>() {
+                        Ok(ident) => config.requires.push(ident),
+                        Err(_) => abort!(lit, "`#[gflags(requires=...)]` expects a field name"),
+                    },
+                    _ => abort!(kv.lit, "`#[gflags(requires=...)]` expects a quoted string"),
+                };
+                continue;
+            }
+
+            if kv.path.is_ident("generate_value_map") {
+                abort!(
+                    kv.lit,
+                    "`#[gflags(generate_value_map)]` does not take a value"
+                );
+            }
+
+            if kv.path.is_ident("generate_is_present") {
+                abort!(
+                    kv.lit,
+                    "`#[gflags(generate_is_present)]` does not take a value"
+                );
+            }
+
+            if kv.path.is_ident("generate_effective") {
+                abort!(
+                    kv.lit,
+                    "`#[gflags(generate_effective)]` does not take a value"
+                );
+            }
+
+            if kv.path.is_ident("generate_diff") {
+                abort!(kv.lit, "`#[gflags(generate_diff)]` does not take a value");
+            }
+
+            if kv.path.is_ident("generate_builder") {
+                abort!(
+                    kv.lit,
+                    "`#[gflags(generate_builder)]` does not take a value"
+                );
+            }
+
+            if kv.path.is_ident("generate_summary") {
+                abort!(
+                    kv.lit,
+                    "`#[gflags(generate_summary)]` does not take a value"
+                );
+            }
+
+            if kv.path.is_ident("generate_help_handler") {
+                abort!(
+                    kv.lit,
+                    "`#[gflags(generate_help_handler)]` does not take a value"
+                );
+            }
+
+            if kv.path.is_ident("generate_clap") {
+                abort!(kv.lit, "`#[gflags(generate_clap)]` does not take a value");
+            }
+
+            if kv.path.is_ident("lowercase") {
+                abort!(kv.lit, "`#[gflags(lowercase)]` does not take a value");
+            }
+
+            if kv.path.is_ident("only_pub") {
+                abort!(kv.lit, "`#[gflags(only_pub)]` does not take a value");
+            }
+
+            if kv.path.is_ident("respect_serde_flatten") {
+                abort!(
+                    kv.lit,
+                    "`#[gflags(respect_serde_flatten)]` does not take a value"
+                );
+            }
+
+            if kv.path.is_ident("secret") {
+                abort!(kv.lit, "`#[gflags(secret)]` does not take a value");
+            }
+
+            if kv.path.is_ident("invert_help") {
+                abort!(kv.lit, "`#[gflags(invert_help)]` does not take a value");
+            }
+
+            if kv.path.is_ident("require_docs") {
+                abort!(kv.lit, "`#[gflags(require_docs)]` does not take a value");
+            }
+
+            if kv.path.is_ident("lenient") {
+                abort!(kv.lit, "`#[gflags(lenient)]` does not take a value");
+            }
+
+            if kv.path.is_ident("skip") {
+                config.skip = match kv.lit {
+                    Lit::Bool(lit) => Some(lit.value),
+                    _ => abort!(kv.lit, "`#[gflags(skip=...)]` expects `true` or `false`"),
+                };
+                continue;
+            }
+
+            if kv.path.is_ident("skip_all") {
+                abort!(kv.lit, "`#[gflags(skip_all)]` does not take a value");
+            }
+
+            if kv.path.is_ident("type") {
+                config.ty = match kv.lit {
+                    Lit::Str(lit) => {
+                        if lit.value().is_empty() {
+                            abort!(
+                                lit,
+                                "`#[gflags(type=...)]` expects a non-empty quoted string"
+                            );
+                        }
+
+                        // Parse as a `Type` so that mistakes are reported here,
+                        // not from inside the `gflags::define!` expansion
+                        let ty = match lit.parse::<Type>() {
+                            Ok(ty) => ty,
+                            Err(e) => {
+                                abort!(lit, "`#[gflags(type=...)]` expects a valid type: {}", e)
+                            }
+                        };
+
+                        // `gflags` does not implement `Value` for slices, and
+                        // the orphan rule stops the caller implementing it
+                        if let Type::Reference(reference) = &ty {
+                            if let Type::Slice(_) = *reference.elem {
+                                abort!(
+                                    lit,
+                                    "`#[gflags(type=...)]` does not support slices, gflags can not parse them; use `&str` or `&OsStr` and convert the value"
+                                );
+                            }
+                        }
+                        Some(quote! { #ty })
+                    }
+                    _ => abort!(kv.lit, "`#[gflags(type=...)]` expects a quoted string"),
+                };
+
+                continue;
+            }
+
+            if kv.path.is_ident("transform") {
+                match kv.lit {
+                    Lit::Str(lit) => match Transform::NAMES
+                        .iter()
+                        .find(|(name, _)| lit.value() == *name)
+                    {
+                        Some((_, transform)) => config.transforms.push(*transform),
+                        None => abort!(
+                            lit,
+                            "`#[gflags(transform=...)]` expects one of: {}",
+                            Transform::NAMES
+                                .iter()
+                                .map(|(name, _)| format!("\"{}\"", name))
+                                .collect::<Vec<_>>()
+                                .join(", ")
+                        ),
+                    },
+                    _ => abort!(kv.lit, "`#[gflags(transform=...)]` expects a quoted string"),
+                };
+                continue;
+            }
+
+            if kv.path.is_ident("acronyms") {
+                match &kv.lit {
+                    Lit::Str(lit) => {
+                        for acronym in lit.value().split(',').map(str::trim) {
+                            if acronym.is_empty()
+                                || !acronym.chars().all(|c| c.is_ascii_alphanumeric())
+                            {
+                                abort!(
+                                    lit,
+                                    "`#[gflags(acronyms=...)]` expects a comma separated list of words"
+                                );
+                            }
+                            config.acronyms.push(acronym.to_string());
+                        }
+                    }
+                    _ => abort!(kv.lit, "`#[gflags(acronyms=...)]` expects a quoted string"),
+                };
+                continue;
+            }
+
+            if kv.path.is_ident("unwrap") {
+                match kv.lit {
+                    Lit::Str(lit) => match lit.parse::<Path>() {
+                        Ok(path) => config.unwrap.push(path_to_string(&path)),
+                        Err(_) => abort!(lit, "`#[gflags(unwrap=...)]` expects a type path"),
+                    },
+                    _ => abort!(kv.lit, "`#[gflags(unwrap=...)]` expects a quoted string"),
+                };
+                continue;
+            }
+
+            if kv.path.is_ident("uppercase") {
+                abort!(kv.lit, "`#[gflags(uppercase)]` does not take a value");
+            }
+
+            if kv.path.is_ident("help_indent") {
+                config.help_indent = match &kv.lit {
+                    Lit::Int(lit) => match lit.base10_parse::<usize>() {
+                        Ok(indent) if indent > 0 => Some(indent),
+                        _ => abort!(
+                            lit,
+                            "`#[gflags(help_indent=...)]` expects a positive integer"
+                        ),
+                    },
+                    _ => abort!(
+                        kv.lit,
+                        "`#[gflags(help_indent=...)]` expects a positive integer"
+                    ),
+                };
+                continue;
+            }
+
+            if kv.path.is_ident("wrap") {
+                config.wrap = match &kv.lit {
+                    Lit::Int(lit) => match lit.base10_parse::<usize>() {
+                        Ok(width) if width > 0 => Some(width),
+                        _ => abort!(lit, "`#[gflags(wrap=...)]` expects a positive integer"),
+                    },
+                    _ => abort!(kv.lit, "`#[gflags(wrap=...)]` expects a positive integer"),
+                };
+                continue;
+            }
+
+            if kv.path.is_ident("visibility") {
+                config.visibility = match kv.lit {
+                    Lit::Str(lit) => {
+                        if lit.value().is_empty() {
+                            abort!(
+                                lit,
+                                "`#[gflags(visibility=...)]` expects a non-empty quoted string"
+                            )
+                        }
+                        Some(lit.parse().unwrap())
+                    }
+                    _ => abort!(
+                        kv.lit,
+                        "`#[gflags(visibility=...)]` expects a quoted string"
+                    ),
+                };
+                continue;
+            }
+
+            config.unknown.push(kv.path);
+        }
+
+        config
+    }
+}
+
+/// Returns the tokens to use for a `default = ...` value
+fn default_from_expr(expr: Expr) -> TokenStream {
+    match expr {
+        Expr::Lit(ExprLit { lit, .. }) => default_from_lit(lit),
+        expr => quote! { = #expr },
+    }
+}
+
+/// Returns the text to show for a default value, given the tokens from
+/// `default_from_expr` or `default_from_lit`. Strings are shown without
+/// quotes.
+fn default_text(default: &TokenStream) -> String {
+    let tokens: TokenStream = default.clone().into_iter().skip(1).collect();
+    match syn::parse2::<Lit>(tokens.clone()) {
+        Ok(Lit::Str(lit)) => lit.value(),
+        _ => tokens.to_string(),
+    }
+}
+
+/// Returns the text of `default` (e.g., `= 100u64`) for `clap`, without any
+/// type suffix, or `None` if it is not a literal.
+fn clap_default(default: &TokenStream) -> Option<String> {
+    let tokens: TokenStream = default.clone().into_iter().skip(1).collect();
+    match syn::parse2::<Lit>(tokens) {
+        Ok(Lit::Str(lit)) => Some(lit.value()),
+        Ok(Lit::Int(lit)) => Some(lit.base10_digits().to_string()),
+        Ok(Lit::Float(lit)) => Some(lit.base10_digits().to_string()),
+        Ok(Lit::Bool(lit)) => Some(lit.value.to_string()),
+        _ => None,
+    }
+}
+
+/// Aborts if `default` is a number with a type suffix (e.g., `100u64`) that
+/// is not the flag's type, `ty`. Types that are not primitive numbers (e.g.,
+/// type aliases) are not checked.
+fn check_default_suffix(default: &TokenStream, ty: &TokenStream) {
+    const NUMBERS: &[&str] = &[
+        "i8", "i16", "i32", "i64", "i128", "isize", "u8", "u16", "u32", "u64", "u128", "usize",
+        "f32", "f64",
+    ];
+
+    let ty = ty.to_string();
+    if !NUMBERS.contains(&ty.as_str()) {
+        return;
+    }
+
+    let tokens: TokenStream = default.clone().into_iter().skip(1).collect();
+    let (suffix, lit) = match syn::parse2::<Lit>(tokens) {
+        Ok(Lit::Int(lit)) => (lit.suffix().to_string(), Lit::Int(lit)),
+        Ok(Lit::Float(lit)) => (lit.suffix().to_string(), Lit::Float(lit)),
+        _ => return,
+    };
+
+    if !suffix.is_empty() && suffix != ty {
+        abort!(
+            lit,
+            "Default value has type `{}`, but the flag has type `{}`",
+            suffix,
+            ty
+        );
+    }
+}
+
+/// Returns the tokens to use for a `default = ...` literal value
+fn default_from_lit(lit: Lit) -> TokenStream {
+    // Raw strings (`r"..."`) are emitted as written. gflags has no flag type
+    // that can hold a byte string, so reject those here rather than leaving
+    // the user with a confusing type error.
+    let lit = match lit {
+        Lit::ByteStr(lit) => abort!(
+            lit,
+            "`#[gflags(default=...)]` does not support byte strings"
+        ),
+        lit => lit,
+    };
+    quote! { = #lit }
+}
+
+impl From<&[Attribute]> for GFlagsAttribute {
+    fn from(attrs: &[Attribute]) -> Self {
+        let mut config: Self = Default::default();
+        for attr in attrs {
+            if !attr.path.is_ident("gflags") {
+                continue;
+            }
+            let parsed_config = GFlagsAttribute::from(attr);
+
+            // Any results in the parsed config overwrite any existing values.
+            // This allows multiple #[gflags(...)] attributes to exist on
+            // a single field
+            if parsed_config.skip.is_some() {
+                config.skip = parsed_config.skip;
+            }
+
+            if parsed_config.skip_all {
+                config.skip_all = true
+            };
+
+            if parsed_config.annotate_source {
+                config.annotate_source = true
+            };
+
+            if parsed_config.generate_apply {
+                config.generate_apply = true
+            };
+
+            if parsed_config.generate_validate {
+                config.generate_validate = true
+            };
+
+            if parsed_config.required {
+                config.required = true
+            };
+
+            if parsed_config.single {
+                config.single = true
+            };
+
+            if parsed_config.optional {
+                config.optional = true
+            };
+
+            if parsed_config.from_file {
+                config.from_file = true
+            };
+
+            if parsed_config.from_str {
+                config.from_str = true
+            };
+
+            if parsed_config.min.is_some() {
+                config.min = parsed_config.min;
+            }
+
+            if parsed_config.max.is_some() {
+                config.max = parsed_config.max;
+            }
+
+            if parsed_config.min_values.is_some() {
+                config.min_values = parsed_config.min_values;
+            }
+
+            if parsed_config.max_values.is_some() {
+                config.max_values = parsed_config.max_values;
+            }
+
+            if parsed_config.default_if_empty.is_some() {
+                config.default_if_empty = parsed_config.default_if_empty;
+            }
+
+            config.requires.extend(parsed_config.requires);
+            config.conflicts_with.extend(parsed_config.conflicts_with);
+            config.attrs.extend(parsed_config.attrs);
+
+            if parsed_config.dedup_prefix {
+                config.dedup_prefix = true
+            };
+
+            if parsed_config.placeholder_shows_default {
+                config.placeholder_shows_default = true
+            };
+
+            if parsed_config.placeholder_from_name {
+                config.placeholder_from_name = true
+            };
+
+            if parsed_config.test_only {
+                config.test_only = true
+            };
+
+            if parsed_config.respect_serde_flatten {
+                config.respect_serde_flatten = true
+            };
+
+            if parsed_config.require_docs {
+                config.require_docs = true
+            };
+
+            if parsed_config.lenient {
+                config.lenient = true
+            };
+
+            config.unknown.extend(parsed_config.unknown);
+            config.spans.extend(parsed_config.spans);
+
+            if parsed_config.export_help {
+                config.export_help = true
+            };
+
+            if parsed_config.export_field_map {
+                config.export_field_map = true
+            };
+
+            if parsed_config.abbrev {
+                config.abbrev = true
+            };
+
+            if parsed_config.both_cases {
+                config.both_cases = true
+            };
+
+            if parsed_config.generate_summary {
+                config.generate_summary = true
+            };
+
+            if parsed_config.generate_help_handler {
+                config.generate_help_handler = true
+            };
+
+            if parsed_config.generate_clap {
+                config.generate_clap = true
+            };
+
+            if parsed_config.generate_effective {
+                config.generate_effective = true
+            };
+
+            if parsed_config.generate_diff {
+                config.generate_diff = true
+            };
+
+            if parsed_config.generate_builder {
+                config.generate_builder = true
+            };
+
+            if parsed_config.generate_value_map {
+                config.generate_value_map = true
+            };
+
+            if parsed_config.generate_is_present {
+                config.generate_is_present = true
+            };
+
+            if parsed_config.only_pub {
+                config.only_pub = true
+            };
+
+            if parsed_config.secret {
+                config.secret = true
+            };
+
+            if parsed_config.invert_help {
+                config.invert_help = true
+            };
+
+            if parsed_config.default.is_some() {
+                config.default = parsed_config.default;
+            }
+
+            if parsed_config.default_expr.is_some() {
+                config.default_expr = parsed_config.default_expr;
+            }
+
+            if parsed_config.parse_with.is_some() {
+                config.parse_with = parsed_config.parse_with;
+            }
+
+            if parsed_config.placeholder.is_some() {
+                config.placeholder = parsed_config.placeholder;
+            }
+
+            if parsed_config.feature.is_some() {
+                config.feature = parsed_config.feature;
+            }
+
+            if parsed_config.help_suffix.is_some() {
+                config.help_suffix = parsed_config.help_suffix;
+            }
+
+            if parsed_config.delimiter.is_some() {
+                config.delimiter = parsed_config.delimiter;
+            }
+
+            config.choices.extend(parsed_config.choices);
+
+            if let Some(name) = parsed_config.name {
+                if config.name_keyword.is_some()
+                    && config.name_keyword != parsed_config.name_keyword
+                {
+                    abort!(
+                        name,
+                        "Use `#[gflags(name=...)]` or `#[gflags(rename=...)]`, not both"
+                    );
+                }
+                config.name = Some(name);
+                config.name_keyword = parsed_config.name_keyword;
+            }
+
+            if parsed_config.help_file.is_some() {
+                config.help_file = parsed_config.help_file;
+            }
+
+            if parsed_config.prefix.is_some() {
+                config.prefix = parsed_config.prefix;
+            }
+
+            if parsed_config.namespace.is_some() {
+                config.namespace = parsed_config.namespace;
+            }
+
+            if parsed_config.wrap.is_some() {
+                config.wrap = parsed_config.wrap;
+            }
+
+            if parsed_config.help_indent.is_some() {
+                config.help_indent = parsed_config.help_indent;
+            }
+
+            if parsed_config.env_prefix.is_some() {
+                config.env_prefix = parsed_config.env_prefix;
+            }
+
+            if parsed_config.group_header.is_some() {
+                config.group_header = parsed_config.group_header;
+            }
+
+            if parsed_config.auto_env {
+                config.auto_env = true
+            };
+
+            if parsed_config.env.is_some() {
+                config.env = parsed_config.env;
+            }
+
+            if parsed_config.crate_path.is_some() {
+                config.crate_path = parsed_config.crate_path;
+            }
+
+            if parsed_config.flag_case.is_some() {
+                config.flag_case = parsed_config.flag_case;
+            }
+
+            if parsed_config.separator.is_some() {
+                config.separator = parsed_config.separator;
+            }
+
+            if parsed_config.prefix_separator.is_some() {
+                config.prefix_separator = parsed_config.prefix_separator;
+            }
+
+            if parsed_config.word_separator.is_some() {
+                config.word_separator = parsed_config.word_separator;
+            }
+
+            if parsed_config.letter_case.is_some() {
+                config.letter_case = parsed_config.letter_case;
+            }
+
+            config.unwrap.extend(parsed_config.unwrap);
+            config.transforms.extend(parsed_config.transforms);
+            config.acronyms.extend(parsed_config.acronyms);
+            config.defaults.extend(parsed_config.defaults);
+
+            if parsed_config.ty.is_some() {
+                config.ty = parsed_config.ty;
+            }
+
+            if parsed_config.visibility.is_some() {
+                config.visibility = parsed_config.visibility;
+            }
+        }
+
+        config
+    }
+}
+
+/// Reports the unknown keywords in `gfa`, as warnings if
+/// `#[gflags(lenient)]` is in effect, and as errors otherwise.
+fn report_unknown(config: &Config, gfa: &GFlagsAttribute) {
+    for path in &gfa.unknown {
+        if config.lenient {
+            emit_warning!(path, "Ignoring unknown keyword `{}`", path_to_string(path));
+        } else {
+            abort!(path, "Invalid keyword `{}`", path_to_string(path));
+        }
+    }
+}
+
+/// Generate a configuration based on `#[gflags(...)` attribute values
+fn config_from_attributes(attrs: &[Attribute]) -> Config {
+    let mut config: Config = Default::default();
+
+    let gfa = GFlagsAttribute::from(attrs);
+
+    config.lenient = gfa.lenient;
+    report_unknown(&config, &gfa);
+
+    if let Some(prefix) = gfa.prefix {
+        config.prefix = prefix;
+    }
+
+    config.namespace = gfa.namespace;
+    config.wrap = gfa.wrap;
+    config.help_indent = gfa.help_indent;
+    config.env_prefix = gfa.env_prefix;
+    config.group_header = gfa.group_header;
+    config.auto_env = gfa.auto_env;
+    if let Some(crate_path) = gfa.crate_path {
+        config.crate_path = crate_path;
+    }
+
+    if let Some(flag_case) = gfa.flag_case {
+        config.flag_case = flag_case;
+    }
+
+    if let Some(separator) = gfa.separator {
+        config.flag_case = separator;
+    }
+
+    // The separators after the prefix and between words default to the one
+    // set above, and can each be changed on their own
+    config.prefix_case = gfa.prefix_separator.unwrap_or(config.flag_case);
+    if let Some(word_separator) = gfa.word_separator {
+        config.flag_case = word_separator;
+    }
+
+    config.letter_case = gfa.letter_case;
+
+    config.require_docs = gfa.require_docs;
+    config.export_help = gfa.export_help;
+    config.export_field_map = gfa.export_field_map;
+    config.unwrap = gfa.unwrap;
+    config.transforms = gfa.transforms;
+    config.acronyms = gfa.acronyms;
+    config.defaults = gfa.defaults;
+    config.abbrev = gfa.abbrev;
+    config.both_cases = gfa.both_cases;
+    config.generate_summary = gfa.generate_summary;
+    config.generate_help_handler = gfa.generate_help_handler;
+    config.generate_clap = gfa.generate_clap;
+    config.generate_effective = gfa.generate_effective;
+    config.generate_diff = gfa.generate_diff;
+    config.generate_builder = gfa.generate_builder;
+    config.generate_value_map = gfa.generate_value_map;
+    config.generate_is_present = gfa.generate_is_present;
+    config.only_pub = gfa.only_pub;
+    config.skip_all = gfa.skip_all;
+    config.annotate_source = gfa.annotate_source;
+    config.generate_apply = gfa.generate_apply;
+    config.generate_validate = gfa.generate_validate;
+    config.dedup_prefix = gfa.dedup_prefix;
+    config.respect_serde_flatten = gfa.respect_serde_flatten;
+    config.placeholder_shows_default = gfa.placeholder_shows_default;
+    config.placeholder_from_name = gfa.placeholder_from_name;
+    config.feature = gfa.feature;
+    config.test_only = gfa.test_only;
+
+    config
+}
+
+/// Returns true if no flag should be generated for the field
+fn is_skipped(config: &Config, field: &Field, gfa: &GFlagsAttribute) -> bool {
+    match gfa.skip {
+        Some(skip) => skip,
+        None => {
+            config.skip_all
+                || (config.only_pub && matches!(field.vis, Visibility::Inherited))
+                || (config.respect_serde_flatten && has_serde_flatten(field))
+        }
+    }
+}
+
+/// Returns true if the field has a `#[serde(flatten)]` attribute
+fn has_serde_flatten(field: &Field) -> bool {
+    field.attrs.iter().any(|attr| {
+        if !attr.path.is_ident("serde") {
+            return false;
+        }
+        match attr.parse_meta() {
+            Ok(Meta::List(list)) => list.nested.iter().any(|nested| match nested {
+                NestedMeta::Meta(Meta::Path(path)) => path.is_ident("flatten"),
+                _ => false,
+            }),
+            _ => false,
+        }
+    })
+}
+
+fn flag_from_field(config: &Config, field: &Field) -> Option<GeneratedFlag> {
+    let gfa = GFlagsAttribute::from(field.attrs.as_slice());
+    if is_skipped(config, field, &gfa) {
+        return None;
+    }
+
+    // Figure out the flag name
+    let name = flag_name(config, field, gfa.name.as_ref());
+    check_flag_name(config, &field.ident, &name);
+
+    if gfa.default.is_some() && gfa.default_expr.is_some() {
+        abort!(
+            keyword_span(&gfa.spans, &["default_expr"], &field.ident),
+            "Use `#[gflags(default=...)]` or `#[gflags(default_expr=...)]`, not both"
+        );
+    }
+
+    // Figure out the default value. A default on the field takes precedence
+    // over one from `#[gflags(defaults(...))]` on the struct
+    let default = match gfa.default {
+        Some(default) => default,
+        _ => config
+            .defaults
+            .iter()
+            .rev()
+            .find(|(ident, _)| field.ident.as_ref() == Some(ident))
+            .map(|(_, default)| default.clone())
+            .unwrap_or_default(),
+    };
+
+    // Figure out the visibility. `inherit` uses the field's own visibility
+    let visibility = match gfa.visibility {
+        Some(visibility) if visibility.to_string() == "inherit" => {
+            let vis = &field.vis;
+            quote! { #vis }
+        }
+        Some(visibility) => visibility,
+        _ => TokenStream::new(),
+    };
+
+    // `Duration` can not implement `gflags::custom::Value` outside `gflags`,
+    // so a `Duration` field has a `&str` flag, parsed in `apply_flags()`
+    let is_duration = gfa.ty.is_none()
+        && match extract_type_from_option(&field.ty, &config.unwrap).unwrap_or(&field.ty) {
+            Type::Path(ty) => ty.path.segments.last().unwrap().ident == "Duration",
+            _ => false,
+        };
 
-use crate::FlagCase::{KebabCase, SnakeCase};
-use proc_macro2::{Ident, Literal, Span, TokenStream, TokenTree};
-use proc_macro_error::{abort, abort_call_site, proc_macro_error};
-use quote::{format_ident, quote};
-use std::collections::HashSet;
-use syn::{
-    punctuated::Punctuated, Attribute, Data, DataStruct, Field, Fields, FieldsNamed,
-    GenericArgument, Lit, Meta, NestedMeta, Path, PathArguments, PathSegment, Token, Type,
-};
+    // `gflags::custom::Value` can not be implemented for `Vec<T>` outside
+    // `gflags`, so a `Vec<T>` field with a delimiter has a flag with a
+    // generated list type, that splits the value on the delimiter
+    let list = match &gfa.delimiter {
+        Some(delimiter) => {
+            if gfa.ty.is_some() || gfa.parse_with.is_some() || gfa.from_str {
+                abort!(
+                    keyword_span(&gfa.spans, &["delimiter"], &field.ident),
+                    "`#[gflags(delimiter=...)]` can not be used with `type`, `parse_with`, or `from_str`"
+                );
+            }
+            if !default.is_empty() || gfa.default_expr.is_some() {
+                abort!(
+                    keyword_span(&gfa.spans, &["default", "default_expr"], &field.ident),
+                    "`#[gflags(delimiter=...)]` can not be used with a default value"
+                );
+            }
+            let ty = extract_type_from_option(&field.ty, &config.unwrap).unwrap_or(&field.ty);
+            match extract_type_from_vec(ty) {
+                Some(item) => Some((format_ident!("{}List", camel_case(&name)), item, delimiter)),
+                None => abort!(
+                    field.ty,
+                    "`#[gflags(delimiter=...)]` requires a `Vec<T>` field"
+                ),
+            }
+        }
+        None => None,
+    };
 
-#[derive(Debug, PartialEq)]
-enum FlagCase {
-    SnakeCase,
-    KebabCase,
-}
+    // A flag with choices has a generated type that only parses one of them
+    let choice = if gfa.choices.is_empty() {
+        None
+    } else {
+        if gfa.ty.is_some() || gfa.parse_with.is_some() || gfa.from_str || list.is_some() {
+            abort!(
+                keyword_span(&gfa.spans, &["choices"], &field.ident),
+                "`#[gflags(choices=...)]` can not be used with `type`, `parse_with`, `from_str`, or `delimiter`"
+            );
+        }
+        if gfa.default_expr.is_some() {
+            abort!(
+                keyword_span(&gfa.spans, &["default_expr"], &field.ident),
+                "`#[gflags(choices=...)]` can not be used with `default_expr`"
+            );
+        }
+        if !default.is_empty() {
+            let tokens: TokenStream = default.clone().into_iter().skip(1).collect();
+            match syn::parse2::<Lit>(tokens.clone()) {
+                Ok(Lit::Str(lit)) if gfa.choices.contains(&lit.value()) => (),
+                _ => abort!(tokens, "The default value must be one of the choices"),
+            }
+        }
+        Some(format_ident!("{}Choice", camel_case(&name)))
+    };
 
-#[derive(Debug)]
-struct Config {
-    /// Prefix to apply to flag names
-    prefix: String,
+    if list.is_none() && (gfa.min_values.is_some() || gfa.max_values.is_some()) {
+        abort!(
+            keyword_span(&gfa.spans, &["min_values", "max_values"], &field.ident),
+            "`#[gflags(min_values=...)]` and `#[gflags(max_values=...)]` require `#[gflags(delimiter=...)]`"
+        );
+    }
 
-    flag_case: FlagCase,
-}
+    if let (Some(min_values), Some(max_values)) = (gfa.min_values, gfa.max_values) {
+        if min_values > max_values {
+            abort!(
+                keyword_span(&gfa.spans, &["min_values"], &field.ident),
+                "`#[gflags(min_values=...)]` must not be greater than `#[gflags(max_values=...)]`"
+            );
+        }
+    }
 
-impl Default for Config {
-    fn default() -> Self {
-        Config {
-            prefix: "".to_string(),
-            flag_case: KebabCase,
+    // Figure out the type
+    let ty = match gfa.ty {
+        Some(ty) => ty,
+        _ if list.is_some() => {
+            let (list_ident, _, _) = list.as_ref().unwrap();
+            quote! { #list_ident }
+        }
+        _ if choice.is_some() => {
+            let choice_ident = choice.as_ref().unwrap();
+            quote! { #choice_ident }
+        }
+        _ => match &field.ty {
+            Type::Path(_) => {
+                // Replace `Option<T>` (or a `#[gflags(unwrap = ...)]` wrapper)
+                // with `T` before proceeding
+                let final_type = match extract_type_from_option(&field.ty, &config.unwrap) {
+                    Some(new_ty) => new_ty,
+                    None => &field.ty,
+                };
+
+                match final_type {
+                    Type::Path(final_type) => {
+                        let ident = &final_type.path.segments.last().unwrap().ident;
+                        if *ident == "String" || is_duration {
+                            quote! { &str }
+                        } else {
+                            quote! { #final_type }
+                        }
+                    }
+                    // A reference like `Option<&str>` is used as is, without
+                    // its lifetime, as flags are `'static`
+                    Type::Reference(reference) if reference.mutability.is_none() => {
+                        let elem = &reference.elem;
+                        quote! { &#elem }
+                    }
+                    _ => abort!(&field.ty, "Unexpected type"),
+                }
+            }
+            _ => abort!(&field.ty, "Unexpected type"),
+        },
+    };
+
+    // `bool` flags take no value, so a placeholder is probably a mistake
+    if let Some(placeholder) = &gfa.placeholder {
+        if ty.to_string() == "bool" {
+            abort!(
+                placeholder,
+                "`#[gflags(placeholder=...)]` can not be used with a `bool` flag, which takes no value"
+            );
         }
     }
-}
 
-fn impl_gflags_macro(ast: &syn::DeriveInput) -> proc_macro::TokenStream {
-    let fields: Vec<&Field> = match &ast.data {
-        Data::Struct(DataStruct {
-            fields: Fields::Named(FieldsNamed { named: fields, .. }),
-            ..
-        }) => fields.into_iter().collect(),
-        _ => abort_call_site!("expected a struct with named fields"),
+    // Derive the placeholder from the flag's name, if requested. `bool`
+    // flags take no value, so have no placeholder
+    let placeholder = match gfa.placeholder {
+        None if config.placeholder_from_name && ty.to_string() != "bool" => Some(LitStr::new(
+            &placeholder_from_name(&name),
+            Span::call_site(),
+        )),
+        placeholder => placeholder,
+    };
+
+    let placeholder_text = placeholder.as_ref().map(LitStr::value);
+
+    // Figure out the placeholder to display, which may show the default
+    let placeholder_display = match placeholder {
+        Some(placeholder) if config.placeholder_shows_default && !default.is_empty() => Some(
+            format!("{}={}", placeholder.value(), default_text(&default)),
+        ),
+        Some(placeholder) => Some(placeholder.value()),
+        _ => None,
     };
 
-    let config = config_from_attributes(&ast.attrs);
+    check_default_suffix(&default, &ty);
 
-    let mut flags: Vec<TokenStream> = vec![];
+    let file_flag = if gfa.from_file {
+        if !config.generate_apply {
+            abort!(
+                keyword_span(&gfa.spans, &["from_file"], &field.ident),
+                "`#[gflags(from_file)]` requires `#[gflags(generate_apply)]` on the struct"
+            );
+        }
+        Some(file_flag_name(config, &name))
+    } else {
+        None
+    };
 
-    for field in fields {
-        let flag = flag_from_field(&config, field);
-        flags.push(flag);
+    let env = env_name(config, gfa.env.as_deref(), &name);
+    if env.is_some() && !config.generate_apply {
+        abort!(
+            keyword_span(&gfa.spans, &["env"], &field.ident),
+            "Reading a flag from the environment requires `#[gflags(generate_apply)]` on the struct"
+        );
     }
 
-    let gen = quote! {
-        #(#flags)*
+    if gfa.default_if_empty.is_some() && !config.generate_apply {
+        abort!(
+            keyword_span(&gfa.spans, &["default_if_empty"], &field.ident),
+            "`#[gflags(default_if_empty=...)]` requires `#[gflags(generate_apply)]` on the struct"
+        );
+    }
+
+    if !config.generate_validate
+        && (gfa.required
+            || gfa.single
+            || gfa.min.is_some()
+            || gfa.max.is_some()
+            || gfa.min_values.is_some()
+            || gfa.max_values.is_some()
+            || !gfa.requires.is_empty()
+            || !gfa.conflicts_with.is_empty())
+    {
+        abort!(
+            keyword_span(
+                &gfa.spans,
+                &[
+                    "required",
+                    "single",
+                    "min",
+                    "max",
+                    "min_values",
+                    "max_values",
+                    "requires",
+                    "conflicts_with"
+                ],
+                &field.ident
+            ),
+            "Validation attributes on a field require `#[gflags(generate_validate)]` on the struct"
+        );
+    }
+
+    // `gflags::define!` takes a `'static` reference to the default value, and
+    // only constants are promoted to `'static`. So store the result of a
+    // `default_expr` in a constant, and use that as the default value.
+    let (default, default_const) = match gfa.default_expr {
+        Some(expr) => {
+            let ident = format_ident!("__GFLAGS_DEFAULT_{}", static_name(&name));
+            (
+                quote! { = #ident },
+                quote! {
+                    #[doc(hidden)]
+                    const #ident: #ty = #expr;
+                },
+            )
+        }
+        None => (default, TokenStream::new()),
     };
 
-    gen.into()
-}
+    if gfa.parse_with.is_some() && gfa.from_str {
+        abort!(
+            keyword_span(&gfa.spans, &["from_str"], &field.ident),
+            "Use `#[gflags(parse_with=...)]` or `#[gflags(from_str)]`, not both"
+        );
+    }
 
-/// Represents a `#[gflags(...)]` attribute on a struct or field.
-#[derive(Debug, Default)]
-struct GFlagsAttribute {
-    /// True if this field should be skipped (do not generate a flag for it)
-    skip: bool,
+    let gflags = &config.crate_path;
 
-    /// Prefix to apply to this flag (or global)
-    prefix: Option<String>,
+    // A field of a type that can not be copied out of the flag's `static`,
+    // like `Box<dyn Trait>`, can have a `&str` flag that is parsed with
+    // `parse_with` in `apply_flags()` instead
+    let parse_in_apply = match &gfa.parse_with {
+        Some(parser) if ty.to_string() == "& str" => Some(parser.clone()),
+        _ => None,
+    };
 
-    /// Casing for this flag
-    flag_case: Option<FlagCase>,
+    // `gflags::custom::Value` can only be implemented for a type once, and
+    // not at all for a type from another crate, so a flag parsed with
+    // `parse_with` or `FromStr` has a generated type that holds the field's
+    // type
+    let parse = match &gfa.parse_with {
+        Some(_) if parse_in_apply.is_some() => None,
+        Some(parser) => Some(quote! { #parser(value) }),
+        None if gfa.from_str => Some(quote! { <#ty as std::str::FromStr>::from_str(value) }),
+        None => None,
+    };
+    let parsed = parse
+        .as_ref()
+        .map(|_| format_ident!("{}Value", camel_case(&name)));
 
-    /// Tokens that define the type to use for this flag
-    ty: Option<TokenStream>,
+    // Define the parsed type, if there is one. `FromStr` is also implemented,
+    // so `apply_flags()` can parse it from an environment variable
+    let parsed_type = match (&parsed, &parse) {
+        (Some(parsed_ident), Some(parse)) => {
+            let doc = format!(" Values of the `--{}` flag.", name);
+            let value_impl = custom_value_impl(
+                gflags,
+                &quote! { #parsed_ident },
+                &name,
+                quote! { value.parse::<#parsed_ident>() },
+            );
+            quote! {
+                #[doc = #doc]
+                #visibility struct #parsed_ident(pub #ty);
 
-    /// Visibility for the flag
-    visibility: Option<TokenStream>,
+                impl std::str::FromStr for #parsed_ident {
+                    type Err = String;
 
-    /// Placeholder to display in the help
-    placeholder: Option<TokenStream>,
+                    fn from_str(value: &str) -> Result<Self, Self::Err> {
+                        #parse
+                            .map(Self)
+                            .map_err(|e| e.to_string())
+                    }
+                }
 
-    /// Default value if the flag is not set
-    default: Option<TokenStream>,
-}
+                #value_impl
+            }
+        }
+        _ => TokenStream::new(),
+    };
 
-impl From<Meta> for GFlagsAttribute {
-    fn from(meta: Meta) -> Self {
-        let meta = match meta {
-            Meta::List(meta) => meta,
-            _ => abort!(meta, "`#[gflags(...)]` expects a parameter list"),
-        };
+    // Define the list type, if there is one. `FromStr` is also implemented,
+    // so `apply_flags()` can parse it from an environment variable
+    let list_type = match &list {
+        Some((list_ident, item, delimiter)) => {
+            let value_impl = custom_value_impl(
+                gflags,
+                &quote! { #list_ident },
+                &name,
+                quote! { value.parse::<#list_ident>() },
+            );
+            let doc = format!(
+                " Values of the `--{}` flag, split on `{:?}`.",
+                name, delimiter
+            );
+            quote! {
+                #[doc = #doc]
+                #[derive(Clone, Debug, Default, PartialEq)]
+                #visibility struct #list_ident(pub Vec<#item>);
+
+                impl std::str::FromStr for #list_ident {
+                    type Err = String;
+
+                    fn from_str(value: &str) -> Result<Self, Self::Err> {
+                        if value.is_empty() {
+                            return Ok(Self(Vec::new()));
+                        }
+                        value
+                            .split(#delimiter)
+                            .map(|item| {
+                                item.parse::<#item>()
+                                    .map_err(|e| format!("invalid item {:?}: {}", item, e))
+                            })
+                            .collect::<Result<Vec<_>, _>>()
+                            .map(Self)
+                    }
+                }
 
-        if meta.nested.is_empty() {
-            abort!(meta, "`#[gflags(...)]` expects a non-empty parameter list");
+                #value_impl
+            }
         }
+        None => TokenStream::new(),
+    };
 
-        let mut config = GFlagsAttribute::default();
+    // Define the choice type, if there is one. It holds the matching choice,
+    // so a default value can be constructed in a `static`
+    let (choice_type, flag_default) = match &choice {
+        Some(choice_ident) => {
+            let doc = format!(" Values of the `--{}` flag.", name);
+            let choices = &gfa.choices;
+            let expected = gfa.choices.join(", ");
+            let value_impl = custom_value_impl(
+                gflags,
+                &quote! { #choice_ident },
+                &name,
+                quote! { value.parse::<#choice_ident>() },
+            );
+            let flag_default = if default.is_empty() {
+                TokenStream::new()
+            } else {
+                let default: TokenStream = default.clone().into_iter().skip(1).collect();
+                quote! { = #choice_ident(#default) }
+            };
+            let choice_type = quote! {
+                #[doc = #doc]
+                #[derive(Clone, Copy, PartialEq)]
+                #visibility struct #choice_ident(pub &'static str);
 
-        let keywords: HashSet<&'static str> = [
-            "default",
-            "placeholder",
-            "prefix",
-            "skip",
-            "type",
-            "visibility",
-        ]
-        .iter()
-        .cloned()
-        .collect();
+                impl std::str::FromStr for #choice_ident {
+                    type Err = String;
 
-        for kv in meta.nested {
-            let kv = match kv {
-                NestedMeta::Meta(Meta::Path(path)) => {
-                    let keyword = path.get_ident().expect("No ident found");
-                    if !keywords.contains(&keyword.to_string().as_ref()) {
-                        abort!(path, "Invalid keyword `{}`", keyword);
+                    fn from_str(value: &str) -> Result<Self, Self::Err> {
+                        match value {
+                            #( #choices => Ok(Self(#choices)), )*
+                            _ => Err(format!("expected one of: {}", #expected)),
+                        }
                     }
+                }
 
-                    if path.is_ident("skip") {
-                        config.skip = true;
-                        break;
+                impl std::fmt::Debug for #choice_ident {
+                    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                        std::fmt::Debug::fmt(self.0, f)
                     }
+                }
 
-                    abort!(path, "Keyword `{}` requires a value", keyword);
+                impl std::fmt::Display for #choice_ident {
+                    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                        f.write_str(self.0)
+                    }
                 }
-                NestedMeta::Meta(Meta::NameValue(kv)) => kv,
-                _ => abort!(kv, "`#[gflags(...)]` expects key=value pairs"),
+
+                #value_impl
             };
+            (choice_type, flag_default)
+        }
+        None => match &parsed {
+            Some(parsed_ident) if !default.is_empty() => {
+                let default: TokenStream = default.clone().into_iter().skip(1).collect();
+                (TokenStream::new(), quote! { = #parsed_ident(#default) })
+            }
+            _ => (TokenStream::new(), default.clone()),
+        },
+    };
 
-            if kv.path.is_ident("default") {
-                let lit = kv.lit;
-                config.default = Some(quote! { = #lit });
-                continue;
+    // Figure out the doc string, if there is one
+    let mut docs: Vec<Literal> = vec![];
+
+    for attr in &field.attrs {
+        if !attr.path.is_ident("doc") {
+            continue;
+        }
+        // Parse the attribute rather than looking for a literal in its
+        // tokens, as a `#[doc = $doc]` from a `macro_rules!` wraps the
+        // literal in an invisible group
+        if let Ok(Meta::NameValue(MetaNameValue {
+            lit: Lit::Str(lit), ..
+        })) = attr.parse_meta()
+        {
+            docs.push(lit.token());
+        }
+    }
+
+    // Describe how to disable a flag that is on by default, if requested.
+    // `gflags` turns a `bool` flag off with `--no` before its name
+    if gfa.invert_help {
+        if ty.to_string() != "bool" || default.to_string() != "= true" {
+            abort!(
+                keyword_span(&gfa.spans, &["invert_help"], &field.ident),
+                "`#[gflags(invert_help)]` requires a `bool` flag with `default = true`"
+            );
+        }
+        let disable = format!("Enabled by default, use --no{} to disable", name);
+        let first = docs.first().map(|doc| match syn::Lit::new(doc.clone()) {
+            Lit::Str(lit) => lit.value(),
+            _ => abort!(doc, "Unexpected doc comment"),
+        });
+        let first = match first {
+            Some(line) => format!("{}. {}", line.trim_end().trim_end_matches('.'), disable),
+            None => format!(" {}", disable),
+        };
+        if docs.is_empty() {
+            docs.push(Literal::string(&first));
+        } else {
+            docs[0] = Literal::string(&first);
+        }
+    }
+
+    // Add the lines of the help file, if there is one. The leading space
+    // matches the space after `///`. `include_str!` makes the crate rebuild
+    // when the file changes
+    let help_file = match &gfa.help_file {
+        Some(help_file) => {
+            let path = match std::env::var("CARGO_MANIFEST_DIR") {
+                Ok(dir) => std::path::Path::new(&dir).join(help_file.value()),
+                Err(_) => std::path::PathBuf::from(help_file.value()),
+            };
+            let contents = match std::fs::read_to_string(&path) {
+                Ok(contents) => contents,
+                Err(e) => abort!(help_file, "Can not read `{}`: {}", path.display(), e),
+            };
+            for line in contents.lines() {
+                docs.push(Literal::string(&format!(" {}", line)));
             }
+            let path = path.to_string_lossy();
+            quote! { const _: &str = include_str!(#path); }
+        }
+        None => TokenStream::new(),
+    };
 
-            if kv.path.is_ident("placeholder") {
-                config.placeholder = match kv.lit {
-                    Lit::Str(lit) => {
-                        if lit.value().is_empty() {
-                            abort!(
-                                lit,
-                                "`#[gflags(placeholder=...)]` expects a non-empty quoted string"
-                            )
-                        }
-                        let tokens = lit.parse::<TokenStream>().unwrap();
-                        Some(quote! { < #tokens > })
-                    }
-                    _ => abort!(
-                        kv.lit,
-                        "`#[gflags(placeholder=...)]` expects a quoted string"
-                    ),
-                };
-                continue;
+    if config.require_docs && docs.is_empty() {
+        abort!(
+            field.ident,
+            "`#[gflags(require_docs)]` requires a doc comment on this field"
+        );
+    }
+
+    // Append the help suffix, if there is one, as another line of the doc
+    // string. The leading space matches the space after `///`.
+    if let Some(help_suffix) = &gfa.help_suffix {
+        docs.push(Literal::string(&format!(" {}", help_suffix)));
+    }
+
+    // Note the range that `validate()` checks, if there is one
+    let range = match (&gfa.min, &gfa.max) {
+        (Some(min), Some(max)) => Some(format!("{}..={}", quote!(#min), quote!(#max))),
+        (Some(min), None) => Some(format!("{}..", quote!(#min))),
+        (None, Some(max)) => Some(format!("..={}", quote!(#max))),
+        (None, None) => None,
+    };
+    if let Some(range) = range {
+        docs.push(Literal::string(&format!(" [range: {}]", range)));
+    }
+
+    // List the choices, if there are any
+    if !gfa.choices.is_empty() {
+        docs.push(Literal::string(&format!(
+            " [choices: {}]",
+            gfa.choices.join(", ")
+        )));
+    }
+
+    // Name the field the flag was generated from, if requested
+    if config.annotate_source {
+        if let Some(ident) = &field.ident {
+            docs.push(Literal::string(&format!(
+                " Generated from {}::{}",
+                config.struct_name, ident
+            )));
+        }
+    }
+
+    // Re-wrap long lines, if requested
+    if let Some(width) = config.wrap {
+        docs = docs
+            .iter()
+            .flat_map(|doc| match syn::Lit::new(doc.clone()) {
+                Lit::Str(lit) => wrap_doc_line(&lit.value(), width),
+                _ => abort!(doc, "Unexpected doc comment"),
+            })
+            .map(|line| Literal::string(&line))
+            .collect();
+    }
+
+    // Indent every line after the first, if requested. Empty lines are left
+    // alone, as `gflags` prints them without any indentation
+    if let Some(indent) = config.help_indent {
+        let indent = " ".repeat(indent);
+        for doc in docs.iter_mut().skip(1) {
+            let line = match syn::Lit::new(doc.clone()) {
+                Lit::Str(lit) => lit.value(),
+                _ => abort!(doc, "Unexpected doc comment"),
+            };
+            let text = line.strip_prefix(' ').unwrap_or(&line);
+            if !text.trim().is_empty() {
+                *doc = Literal::string(&format!(" {}{}", indent, text));
             }
+        }
+    }
 
-            if kv.path.is_ident("prefix") {
-                let mut prefix = match kv.lit {
-                    Lit::Str(lit) => {
-                        if lit.value().is_empty() {
-                            abort!(
-                                lit,
-                                "`#[gflags(prefix=...)]` expects a non-empty quoted string"
-                            );
-                        }
+    // Figure out the help text, stripping the leading space from each line
+    // the same way `gflags` does
+    let help_text = docs
+        .iter()
+        .map(|doc| match syn::Lit::new(doc.clone()) {
+            Lit::Str(lit) => {
+                let line = lit.value();
+                line.strip_prefix(' ').unwrap_or(&line).to_string()
+            }
+            _ => abort!(doc, "Unexpected doc comment"),
+        })
+        .collect::<Vec<String>>()
+        .join("\n");
 
-                        lit.value()
-                    }
-                    _ => abort!(kv.lit, "`#[gflags(prefix=...)]` expects a quoted string"),
-                };
+    // Figure out the help text constant, if requested
+    let help = if config.export_help {
+        let help_ident = format_ident!("{}_HELP", static_name(&name));
+        let help = &help_text;
+        quote! {
+            pub const #help_ident: &str = #help;
+        }
+    } else {
+        TokenStream::new()
+    };
 
-                if prefix.ends_with('_') {
-                    config.flag_case = Some(SnakeCase);
-                    prefix.pop();
-                }
+    let has_default = !default.is_empty();
+    let default_value = clap_default(&default);
+    let is_bool = ty.to_string() == "bool";
 
-                if prefix.ends_with('-') {
-                    config.flag_case = Some(KebabCase);
-                    prefix.pop();
-                }
+    // The flag has the parsed type, if there is one, holding the field's type
+    let flag_ty = match &parsed {
+        Some(parsed_ident) => quote! { #parsed_ident },
+        None => ty.clone(),
+    };
+
+    // Defines a `static` for the flag called `name`. The abbreviation and the
+    // flag with the other separator are defined the same way, with the same
+    // help, type, and default, but a different name
+    let attrs = &gfa.attrs;
+    let define_static = |ident: Ident, name: &str| {
+        define_flag(
+            gflags,
+            FlagStatic {
+                attrs,
+                vis: &visibility,
+                ident,
+                name,
+                doc: docs.clone(),
+                placeholder: placeholder_display.as_deref(),
+                ty: &flag_ty,
+                default: if has_default {
+                    Some(flag_default.clone().into_iter().skip(1).collect())
+                } else {
+                    None
+                },
+            },
+        )
+    };
+    let define = define_static(format_ident!("{}", static_name(&name)), &name);
+
+    // Define the abbreviated flag, if requested
+    let mut aliases = vec![];
+    let abbrev = abbreviation(config, &name);
+    let abbrev = if (config.abbrev || gfa.abbrev) && abbrev != name {
+        let abbrev_ident = format_ident!("{}", static_name(&abbrev));
+        aliases.push((abbrev.clone(), abbrev_ident.clone()));
+        define_static(abbrev_ident, &abbrev)
+    } else {
+        TokenStream::new()
+    };
+
+    // Define the flag with the other separator, if requested. Both flags
+    // would have the same static name, so this one has a suffix
+    let other_case_define = match other_case_flag(&name) {
+        Some((other_name, other_static)) if config.both_cases => {
+            let other_ident = format_ident!("{}", other_static);
+            aliases.push((other_name.clone(), other_ident.clone()));
+            define_static(other_ident, &other_name)
+        }
+        _ => TokenStream::new(),
+    };
+
+    // Define the flag to read the value from a file, if requested
+    let file_define = match &file_flag {
+        Some(file_name) => define_flag(
+            gflags,
+            FlagStatic {
+                attrs: &[],
+                vis: &visibility,
+                ident: format_ident!("{}", static_name(file_name)),
+                name: file_name,
+                doc: vec![Literal::string(&format!(
+                    " File to read the value of --{} from",
+                    name
+                ))],
+                placeholder: Some("FILE"),
+                ty: &quote! { &str },
+                default: None,
+            },
+        ),
+        None => TokenStream::new(),
+    };
+
+    // Construct the macro call
+    let gen = quote! {
+        #default_const
+
+        #define
+
+        #file_define
+
+        #abbrev
+
+        #other_case_define
+
+        #list_type
+
+        #choice_type
+
+        #parsed_type
 
-                config.prefix = Some(prefix);
-                continue;
-            }
+        #help_file
 
-            if kv.path.is_ident("skip") {
-                abort!(kv.lit, "`#[gflags(skip)]` does not take a value");
-            }
+        #help
+    };
 
-            if kv.path.is_ident("type") {
-                config.ty = match kv.lit {
-                    Lit::Str(lit) => {
-                        if lit.value().is_empty() {
-                            abort!(
-                                lit,
-                                "`#[gflags(type=...)]` expects a non-empty quoted string"
-                            );
-                        }
+    // Gate the flag, and everything generated for it, in the same way as
+    // the field
+    let cfgs: Vec<Attribute> = field
+        .attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("cfg"))
+        .cloned()
+        .collect();
+    let gen = if cfgs.is_empty() {
+        gen
+    } else {
+        gate_items(gen, &cfgs)
+    };
 
-                        Some(lit.parse().unwrap())
-                    }
-                    _ => abort!(kv.lit, "`#[gflags(type=...)]` expects a quoted string"),
-                };
+    // `apply_flags()` sets an `Option<T>` field to `Some(value)`, but has no
+    // way to construct a `#[gflags(unwrap = ...)]` wrapper type
+    let is_option = gfa.optional || extract_type_from_option(&field.ty, &config.unwrap).is_some();
+    if config.generate_apply
+        && is_option
+        && !gfa.optional
+        && extract_type_from_option(&field.ty, &[]).is_none()
+    {
+        abort!(
+            field.ty,
+            "`#[gflags(generate_apply)]` can only set `Option<T>` fields, not fields with a `#[gflags(unwrap = ...)]` type";
+            help = "skip the field with `#[gflags(skip)]`"
+        );
+    }
 
-                continue;
-            }
+    Some(GeneratedFlag {
+        ident: format_ident!("{}", static_name(&name)),
+        aliases,
+        name,
+        help: help_text,
+        placeholder: placeholder_text,
+        has_default,
+        default_value,
+        // The value of a parsed flag is compared to the default without the
+        // parsed type, which need not implement `PartialEq`
+        default: if has_default && parsed.is_some() {
+            Some(default.into_iter().skip(1).collect())
+        } else if has_default {
+            Some(flag_default.into_iter().skip(1).collect())
+        } else {
+            None
+        },
+        is_bool,
+        is_duration,
+        parse_in_apply,
+        is_list: list.is_some(),
+        is_choice: choice.is_some(),
+        is_parsed: parsed.is_some(),
+        env,
+        file_flag: file_flag.map(|file_name| {
+            let ident = format_ident!("{}", static_name(&file_name));
+            (file_name, ident)
+        }),
+        ty: flag_ty,
+        secret: gfa.secret,
+        field: field_ident(field).clone(),
+        is_option,
+        required: gfa.required,
+        single: gfa.single,
+        min: gfa.min,
+        max: gfa.max,
+        min_values: gfa.min_values,
+        max_values: gfa.max_values,
+        default_if_empty: gfa.default_if_empty,
+        requires: gfa.requires,
+        conflicts_with: gfa.conflicts_with,
+        cfgs,
+        tokens: gen,
+    })
+}
 
-            if kv.path.is_ident("visibility") {
-                config.visibility = match kv.lit {
-                    Lit::Str(lit) => {
-                        if lit.value().is_empty() {
-                            abort!(
-                                lit,
-                                "`#[gflags(visibility=...)]` expects a non-empty quoted string"
-                            )
-                        }
-                        Some(lit.parse().unwrap())
-                    }
-                    _ => abort!(
-                        kv.lit,
-                        "`#[gflags(visibility=...)]` expects a quoted string"
-                    ),
-                };
-                continue;
+/// Returns an implementation of `gflags::custom::Value` for `ty`, the type
+/// generated for the flag called `name`. `parse` is an expression that parses
+/// `value`, a `&str`, into a `Result` whose error implements `Display`. Errors
+/// are reported as `invalid value "..." for --name: error`.
+///
+/// `ty` must only be used by this flag, so that the error names the flag that
+/// failed to parse, not another flag of the same type.
+fn custom_value_impl(
+    gflags: &Path,
+    ty: &TokenStream,
+    name: &str,
+    parse: TokenStream,
+) -> TokenStream {
+    quote! {
+        impl #gflags::custom::Value for #ty {
+            fn parse(arg: #gflags::custom::Arg) -> #gflags::custom::Result<Self> {
+                let value = arg.get_str();
+                #parse.map_err(|e| {
+                    #gflags::custom::Error::new(format!(
+                        "invalid value {:?} for --{}: {}",
+                        value, #name, e
+                    ))
+                })
             }
-
-            abort!(
-                kv.path,
-                "Invalid keyword `{}`",
-                kv.path.get_ident().unwrap()
-            );
         }
+    }
+}
 
-        config
+/// Splits a doc comment line into lines of at most `width` characters,
+/// breaking at whitespace. Words longer than `width` are not split. Each
+/// line keeps the single leading space from `///`.
+fn wrap_doc_line(line: &str, width: usize) -> Vec<String> {
+    let text = line.strip_prefix(' ').unwrap_or(line);
+    if text.chars().count() <= width {
+        return vec![line.to_string()];
+    }
+
+    let mut lines = vec![];
+    let mut current = String::new();
+    for word in text.split_whitespace() {
+        if !current.is_empty() && current.chars().count() + 1 + word.chars().count() > width {
+            lines.push(format!(" {}", current));
+            current.clear();
+        }
+        if !current.is_empty() {
+            current.push(' ');
+        }
+        current.push_str(word);
     }
+    if !current.is_empty() {
+        lines.push(format!(" {}", current));
+    }
+    lines
 }
 
-impl From<&[Attribute]> for GFlagsAttribute {
-    fn from(attrs: &[Attribute]) -> Self {
-        let mut config: Self = Default::default();
-        for attr in attrs {
-            match attr.parse_meta() {
-                Ok(meta) => {
-                    if !meta.path().is_ident("gflags") {
-                        continue;
-                    }
-                    let parsed_config = GFlagsAttribute::from(meta);
+/// Returns a placeholder for the flag called `name`, the last word of the
+/// name in upper case (e.g., `log-dir` gives `DIR`).
+fn placeholder_from_name(name: &str) -> String {
+    name.rsplit(['-', '_'])
+        .next()
+        .unwrap_or(name)
+        .to_uppercase()
+}
 
-                    // Any results in the parsed config overwrite any existing values.
-                    // This allows multiple #[gflags(...)] attributes to exist on
-                    // a single field
-                    if parsed_config.skip {
-                        config.skip = true
-                    };
+/// Returns the name of the environment variable to read the flag called
+/// `name` from, if any. This is the field's `env` name, or the flag's name
+/// in upper case if `auto_env` is set on the struct, after `env_prefix`.
+fn env_name(config: &Config, env: Option<&str>, name: &str) -> Option<String> {
+    let env = match env {
+        Some(env) => env.to_string(),
+        None if config.auto_env => static_name(name),
+        None => return None,
+    };
 
-                    if parsed_config.default.is_some() {
-                        config.default = parsed_config.default;
-                    }
+    match &config.env_prefix {
+        Some(env_prefix) => Some(format!("{}_{}", env_prefix, env)),
+        None => Some(env),
+    }
+}
 
-                    if parsed_config.placeholder.is_some() {
-                        config.placeholder = parsed_config.placeholder;
-                    }
+/// Returns the identifier of `field`. Only named fields have one, so abort
+/// with an error on the field, rather than panicking, for any other field.
+fn field_ident(field: &Field) -> &Ident {
+    match &field.ident {
+        Some(ident) => ident,
+        None => abort!(field, "Flags can only be generated for named fields"),
+    }
+}
 
-                    if parsed_config.prefix.is_some() {
-                        config.prefix = parsed_config.prefix;
-                    }
+/// Returns the name of the flag for `field`, without the leading `--`.
+/// `rename`, from `#[gflags(name = "...")]` or `#[gflags(rename = "...")]`,
+/// replaces the field's name, and is not transformed.
+fn flag_name(config: &Config, field: &Field, rename: Option<&LitStr>) -> String {
+    // Use `unraw()` so a field like `r#type` gives a flag called `type`
+    let mut field_name = field_ident(field).unraw().to_string();
+    if !config.acronyms.is_empty() {
+        field_name = snake_case_words(&field_name, &config.acronyms);
+    }
+    prefixed_flag_name(config, field_name, rename)
+}
 
-                    if parsed_config.flag_case.is_some() {
-                        config.flag_case = parsed_config.flag_case;
-                    }
+/// Returns the name of the flag for `field_name`, a `snake_case` name, with
+/// the namespace and prefix from `config`, and without the leading `--`.
+fn prefixed_flag_name(config: &Config, field_name: String, rename: Option<&LitStr>) -> String {
+    let separator = config.flag_case.separator();
+    let prefix_separator = config.prefix_case.separator();
+    let mut name = String::new();
+    if let Some(namespace) = &config.namespace {
+        name.push_str(namespace);
+        name.push(prefix_separator);
+    }
+    // Normalise the prefix's case, so `LOG_` gives `log_to_stderr`, not
+    // `LOG_to_stderr`. `#[gflags(uppercase)]` converts it back below
+    let prefix = config.prefix.to_lowercase();
+    if !prefix.is_empty() {
+        name.push_str(&prefix);
+        name.push(prefix_separator);
+    }
+    let mut field_name = field_name;
+    for transform in &config.transforms {
+        field_name = transform.apply(&field_name);
+    }
 
-                    if parsed_config.ty.is_some() {
-                        config.ty = parsed_config.ty;
-                    }
+    if let Some(rename) = rename {
+        field_name = rename.value().replace('-', "_");
+    }
 
-                    if parsed_config.visibility.is_some() {
-                        config.visibility = parsed_config.visibility;
-                    }
-                }
-                Err(e) => abort!(attr, e),
-            }
+    // Strip the prefix from the start of the field name (e.g., `log_dir`
+    // with the prefix `log-`), so it does not appear in the flag name twice
+    if rename.is_none() && config.dedup_prefix && !prefix.is_empty() {
+        let prefix = format!("{}_", prefix.replace('-', "_"));
+        if field_name.len() > prefix.len() && field_name.starts_with(&prefix) {
+            field_name = field_name[prefix.len()..].to_string();
         }
+    }
 
-        config
+    name.push_str(&field_name.replace('_', &separator.to_string()));
+
+    match config.letter_case {
+        Some(LetterCase::Lowercase) => name.to_lowercase(),
+        Some(LetterCase::Uppercase) => name.to_uppercase(),
+        None => name,
     }
 }
 
-/// Generate a configuration based on `#[gflags(...)` attribute values
-fn config_from_attributes(attrs: &[Attribute]) -> Config {
-    let mut config: Config = Default::default();
-
-    let gfa = GFlagsAttribute::from(attrs);
+/// Aborts, at `at`, if `name` is not a valid flag name. Names are made of words of
+/// ASCII letters and digits, separated by the flag's separators. Letters must
+/// be lowercase, unless `#[gflags(uppercase)]` is in effect. As with
+/// `gflags::define!`, which takes each `-` separated part of the name as an
+/// identifier, a part can not start with a digit.
+fn check_flag_name<T: ToTokens>(config: &Config, at: &T, name: &str) {
+    let separator = config.flag_case.separator();
+    let prefix_separator = config.prefix_case.separator();
+    let separators = [separator, prefix_separator];
+    let (is_letter, letters): (fn(&char) -> bool, &str) = match config.letter_case {
+        Some(LetterCase::Uppercase) => (char::is_ascii_uppercase, "A-Z"),
+        _ => (char::is_ascii_lowercase, "a-z"),
+    };
 
-    if gfa.prefix.is_some() {
-        config.prefix = gfa.prefix.unwrap();
+    if let Some(c) = name
+        .chars()
+        .find(|c| !(is_letter(c) || c.is_ascii_digit() || separators.contains(c)))
+    {
+        abort!(
+            at,
+            "Flag name `--{}` contains invalid character `{}`, expected [{}0-9{}]",
+            name,
+            c,
+            letters,
+            separator
+        );
     }
 
-    if gfa.flag_case.is_some() {
-        config.flag_case = gfa.flag_case.unwrap();
+    if name.split(separators).any(str::is_empty) {
+        abort!(
+            at,
+            "Flag name `--{}` contains an empty word, check for leading, trailing, or repeated `{}`",
+            name,
+            separator
+        );
     }
 
-    config
+    if let Some(word) = name
+        .split('-')
+        .find(|word| word.starts_with(|c: char| c.is_ascii_digit()))
+    {
+        abort!(
+            at,
+            "Flag name `--{}` contains the word `{}`, which starts with a digit",
+            name,
+            word
+        );
+    }
 }
 
-fn flag_from_field(config: &Config, field: &Field) -> TokenStream {
-    let gfa = GFlagsAttribute::from(field.attrs.as_ref());
-    if gfa.skip {
-        return TokenStream::new();
+/// Returns the casing for the separator given as `lit` to the `keyword`
+/// attribute, aborting if it is not `-` or `_`.
+fn separator_from_lit(keyword: &str, lit: Lit) -> FlagCase {
+    match lit {
+        Lit::Str(lit) => match lit.value().as_ref() {
+            "_" => SnakeCase,
+            "-" => KebabCase,
+            _ => abort!(
+                lit,
+                "`#[gflags({}=...)]` expects \"-\" or \"_\", gflags does not accept other separators in flag names",
+                keyword
+            ),
+        },
+        _ => abort!(lit, "`#[gflags({}=...)]` expects a quoted string", keyword),
     }
+}
 
-    // Figure out the flag name
-    let flag_name = if config.flag_case == SnakeCase {
-        let ident = if !config.prefix.is_empty() {
-            format_ident!(
-                "{}_{}",
-                config.prefix,
-                field
-                    .ident
-                    .as_ref()
-                    .expect("Unwrapping field.ident (prefix) failed")
-            )
-        } else {
-            field
-                .ident
-                .as_ref()
-                .expect("Unwrapping field.ident (no-prefix) failed")
-                .clone()
-        };
-        quote! {--#ident}
-    } else {
-        let span = Span::call_site();
-        let mut segments: Punctuated<Ident, Token![-]> = Punctuated::new();
-        if !config.prefix.is_empty() {
-            segments.push(Ident::new(&config.prefix, span));
-        }
+/// Returns the span of the first of `keywords` in `spans`, the keywords
+/// given in a field's attributes, or the span of `fallback` if none of them
+/// were given.
+fn keyword_span<T: Spanned>(
+    spans: &HashMap<String, Span>,
+    keywords: &[&str],
+    fallback: &T,
+) -> Span {
+    keywords
+        .iter()
+        .find_map(|keyword| spans.get(*keyword).copied())
+        .unwrap_or_else(|| fallback.span())
+}
 
-        let field = field.ident.as_ref().unwrap().to_string();
-        for part in field.split('_') {
-            segments.push(Ident::new(part, span));
-        }
-        quote! {--#segments}
+/// Returns the name of the flag that names a file to read the value of the
+/// flag called `name` from (e.g., `log-token` gives `log-token-file`).
+fn file_flag_name(config: &Config, name: &str) -> String {
+    let file = match config.letter_case {
+        Some(LetterCase::Uppercase) => "FILE",
+        _ => "file",
     };
+    format!("{}{}{}", name, config.flag_case.separator(), file)
+}
 
-    // Figure out the default value
-    let default = match gfa.default {
-        Some(default) => default,
-        _ => TokenStream::new(),
-    };
+/// A flag `static` to define with `define_flag()`.
+struct FlagStatic<'a> {
+    /// Attributes to add to the `static`
+    attrs: &'a [Attribute],
 
-    // Figure out the placeholder
-    let placeholder = match gfa.placeholder {
-        Some(placeholder) => placeholder,
-        _ => TokenStream::new(),
-    };
+    /// Visibility of the `static`
+    vis: &'a TokenStream,
 
-    // Figure out the visibility
-    let visibility = match gfa.visibility {
-        Some(visibility) => visibility,
-        _ => TokenStream::new(),
+    /// Identifier of the `static`
+    ident: Ident,
+
+    /// Name of the flag, without the leading `--`
+    name: &'a str,
+
+    /// Lines of the flag's doc comment
+    doc: Vec<Literal>,
+
+    /// Placeholder to display in the help, if any
+    placeholder: Option<&'a str>,
+
+    /// Type of the flag
+    ty: &'a TokenStream,
+
+    /// Default value of the flag, if it has one
+    default: Option<TokenStream>,
+}
+
+/// Returns the tokens that define `flag` with `gflags::define!`.
+///
+/// `gflags::define!` ignores attributes other than doc comments, and names
+/// the `static` after the flag. So a flag with attributes, or whose `static`
+/// has a different name, is defined in a module. The attributes are put on
+/// the module, and on a `use` that brings the `static` into scope with its
+/// name. The module imports everything from its parent, so the flag's type
+/// and default can be named there.
+fn define_flag(gflags: &Path, flag: FlagStatic) -> TokenStream {
+    let FlagStatic {
+        attrs,
+        vis,
+        ident,
+        name,
+        doc,
+        placeholder,
+        ty,
+        default,
+    } = flag;
+
+    let flag_name = flag_name_tokens(name);
+    let placeholder = match placeholder {
+        Some(placeholder) => quote! { <#placeholder> },
+        None => TokenStream::new(),
+    };
+    let default = match default {
+        Some(default) => quote! { = #default },
+        None => TokenStream::new(),
     };
 
-    // Figure out the type
-    let ty = match gfa.ty {
-        Some(ty) => ty,
-        _ => match &field.ty {
-            Type::Path(ty) => {
-                let mut last = ty.path.segments.last().unwrap();
-                let mut ident = &last.ident;
-
-                let mut final_type = ty.clone();
-
-                // Replace `Option<T>` with `T` before proceeding
-                if *ident == "Option" {
-                    let option_type = syn::Type::from(final_type);
-
-                    let new_ty = extract_type_from_option(&option_type);
-                    match new_ty {
-                        Some(Type::Path(new_ty)) => {
-                            final_type = new_ty.clone();
-                            last = final_type.path.segments.last().unwrap();
-                            ident = &last.ident;
-                        }
-                        _ => abort!(&field.ty, "Unexpected type"),
-                    }
-                }
+    let static_ident = format_ident!("{}", static_name(name));
+    if attrs.is_empty() && ident == static_ident {
+        return quote! {
+            #gflags::define! {
+                #( #[doc = #doc] )*
+                #vis #flag_name #placeholder: #ty #default
+            }
+        };
+    }
 
-                if *ident == "String" {
-                    quote! { &str }
-                } else {
-                    quote! { #final_type }
-                }
+    let module = format_ident!("__gflags_{}", ident.to_string().to_lowercase());
+    quote! {
+        #(#attrs)*
+        #[doc(hidden)]
+        mod #module {
+            #[allow(unused_imports)]
+            use super::*;
+
+            #gflags::define! {
+                #( #[doc = #doc] )*
+                pub #flag_name #placeholder: #ty #default
             }
-            _ => abort!(&field.ty, "Unexpected type"),
-        },
+        }
+
+        #(#attrs)*
+        #vis use #module::#static_ident as #ident;
+    }
+}
+
+/// Returns the tokens for the flag called `name` (e.g., `--log-dir`), as
+/// `gflags::define!` expects the name as `-` separated identifiers.
+fn flag_name_tokens(name: &str) -> TokenStream {
+    let span = Span::call_site();
+    let segments: Punctuated<Ident, Token![-]> = name
+        .split('-')
+        .map(|segment| Ident::new(segment, span))
+        .collect();
+    quote! {--#segments}
+}
+
+/// Returns the name of the flag called `name` with the other separator, and
+/// the name of its variable, for `#[gflags(both_cases)]` (e.g., `log-dir`
+/// gives `log_dir` and `LOG_DIR_SNAKE`). Returns `None` if the name has no
+/// separator.
+fn other_case_flag(name: &str) -> Option<(String, String)> {
+    let (other_name, suffix) = if name.contains('-') {
+        (name.replace('-', "_"), "SNAKE")
+    } else if name.contains('_') {
+        (name.replace('_', "-"), "KEBAB")
+    } else {
+        return None;
     };
+    Some((other_name, format!("{}_{}", static_name(name), suffix)))
+}
 
-    // Figure out the doc string, if there is one
-    let mut docs: Vec<Literal> = vec![];
+/// Returns the abbreviation for the flag called `name`, made from the first
+/// character of each word in the name (e.g., `log-to-stderr` is `lts`).
+fn abbreviation(config: &Config, name: &str) -> String {
+    let separators = [config.flag_case.separator(), config.prefix_case.separator()];
+    name.split(separators)
+        .filter_map(|word| word.chars().next())
+        .collect()
+}
 
-    for attr in &field.attrs {
-        if !attr.path.is_ident("doc") {
+/// Returns the identifiers in `path` joined with `::`, ignoring any generic
+/// arguments and any leading `::` (so `::std::option::Option<T>` is
+/// `std::option::Option`).
+fn path_to_string(path: &Path) -> String {
+    path.segments
+        .iter()
+        .map(|segment| segment.ident.to_string())
+        .collect::<Vec<_>>()
+        .join("::")
+}
+
+/// Returns the name of the static for a flag called `name`, the same name
+/// that `gflags::define!` would use.
+fn static_name(name: &str) -> String {
+    name.replace('-', "_").to_uppercase()
+}
+
+/// Returns `name` split into lowercase words joined with `_`. Words are
+/// split at `_` and at changes of case, so `HTTPServer` and `http_server`
+/// are both `http_server`. Each of `acronyms` found at the start of a word
+/// is kept as a single word, so with `OAuth` `getOAuthToken` is
+/// `get_oauth_token`, not `get_o_auth_token`.
+fn snake_case_words(name: &str, acronyms: &[String]) -> String {
+    // Check longer acronyms first, so `HTTPS` is preferred to `HTTP`
+    let mut acronyms: Vec<&str> = acronyms.iter().map(String::as_str).collect();
+    acronyms.sort_by_key(|acronym| std::cmp::Reverse(acronym.len()));
+
+    let chars: Vec<char> = name.chars().collect();
+    let mut words: Vec<String> = vec![];
+    let mut word = String::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        if c == '_' {
+            if !word.is_empty() {
+                words.push(std::mem::take(&mut word));
+            }
+            i += 1;
             continue;
         }
-        let tokens = attr.tokens.clone();
-        for token in tokens {
-            if let TokenTree::Literal(l) = token {
-                docs.push(l);
+
+        // An acronym starts a word at the start of the name, after `_`, or
+        // where its first letter is a change of case
+        let rest: String = chars[i..].iter().collect();
+        let starts_word = |acronym: &str| match word.chars().last() {
+            Some(last) => !last.is_uppercase() && acronym.starts_with(|c: char| c.is_uppercase()),
+            None => true,
+        };
+        if let Some(acronym) = acronyms
+            .iter()
+            .find(|acronym| rest.starts_with(*acronym) && starts_word(acronym))
+        {
+            if !word.is_empty() {
+                words.push(std::mem::take(&mut word));
             }
+            words.push(acronym.to_string());
+            i += acronym.chars().count();
+            continue;
         }
-    }
 
-    // Construct the macro call
-    let gen = quote! {
-        gflags::define! {
-            #( #[doc = #docs])*
-            #visibility #flag_name #placeholder: #ty #default
+        if c.is_uppercase() {
+            if let Some(last) = word.chars().last() {
+                // `logDir` splits before `D`, and `HTTPServer` before `S`
+                let next_is_lower = chars.get(i + 1).is_some_and(|c| c.is_lowercase());
+                if !last.is_uppercase() || next_is_lower {
+                    words.push(std::mem::take(&mut word));
+                }
+            }
         }
-    };
+        word.push(c);
+        i += 1;
+    }
+    if !word.is_empty() {
+        words.push(word);
+    }
+
+    words.join("_").to_lowercase()
+}
+
+/// Returns `name`, a flag name, in `CamelCase` (e.g., `log-tags` becomes
+/// `LogTags`).
+fn camel_case(name: &str) -> String {
+    name.split(|c: char| !c.is_alphanumeric())
+        .flat_map(|word| {
+            let mut chars = word.chars();
+            chars
+                .next()
+                .into_iter()
+                .flat_map(char::to_uppercase)
+                .chain(chars)
+        })
+        .collect()
+}
 
-    gen
+/// Given a `syn::Type` that is a `Vec<T>`, return the `syn::Type` for the
+/// `T`, or `None` if it is not a `Vec<T>`.
+fn extract_type_from_vec(ty: &syn::Type) -> Option<&syn::Type> {
+    let path = match ty {
+        Type::Path(typepath) if typepath.qself.is_none() => &typepath.path,
+        _ => return None,
+    };
+    let segment = path.segments.last()?;
+    if segment.ident != "Vec" {
+        return None;
+    }
+    match &segment.arguments {
+        PathArguments::AngleBracketed(params) => match params.args.first() {
+            Some(GenericArgument::Type(ty)) => Some(ty),
+            _ => None,
+        },
+        _ => None,
+    }
 }
 
 /// Given a `syn::Type` that is an `Option<T>`, return the `syn::Type` for the
 /// `T`, or `None` if it's not a `syn::Type::Path`.
 ///
+/// `wrappers` are the paths of additional single-generic types that should
+/// be unwrapped in the same way as `Option`.
+///
 /// https://stackoverflow.com/questions/55271857/how-can-i-get-the-t-from-an-optiont-when-using-syn
-fn extract_type_from_option(ty: &syn::Type) -> Option<&syn::Type> {
+fn extract_type_from_option<'a>(ty: &'a syn::Type, wrappers: &[String]) -> Option<&'a syn::Type> {
     fn extract_type_path(ty: &syn::Type) -> Option<&Path> {
         match *ty {
             syn::Type::Path(ref typepath) if typepath.qself.is_none() => Some(&typepath.path),
@@ -788,20 +6454,18 @@ fn extract_type_from_option(ty: &syn::Type) -> Option<&syn::Type> {
         }
     }
 
-    fn extract_option_segment(path: &Path) -> Option<&PathSegment> {
-        let idents_of_path = path.segments.iter().fold(String::new(), |mut acc, v| {
-            acc.push_str(&v.ident.to_string());
-            acc.push('|');
-            acc
-        });
-        vec!["Option|", "std|option|Option|", "core|option|Option|"]
-            .into_iter()
+    fn extract_option_segment<'a>(path: &'a Path, wrappers: &[String]) -> Option<&'a PathSegment> {
+        let idents_of_path = path_to_string(path);
+        ["Option", "std::option::Option", "core::option::Option"]
+            .iter()
+            .copied()
+            .chain(wrappers.iter().map(String::as_str))
             .find(|s| idents_of_path == *s)
             .and_then(|_| path.segments.last())
     }
 
     extract_type_path(ty)
-        .and_then(|path| extract_option_segment(path))
+        .and_then(|path| extract_option_segment(path, wrappers))
         .and_then(|pair_path_segment| {
             let type_params = &pair_path_segment.arguments;
             // It should have only one angle-bracketed param ("<String>"):
@@ -820,17 +6484,159 @@ fn extract_type_from_option(ty: &syn::Type) -> Option<&syn::Type> {
 ///
 /// `#[gflags(prefix = "...")]` -- apply this prefix to flag names
 ///
+/// `#[gflags(dedup_prefix)]` -- remove the prefix from the start of field names
+///
+/// `#[gflags(namespace = "...")]` -- apply this namespace to flag names, before the prefix
+///
+/// `#[gflags(lowercase)]` -- convert flag names to lowercase
+///
+/// `#[gflags(uppercase)]` -- convert flag names to uppercase
+///
+/// `#[gflags(acronyms = "...")]` -- split field names into words, keeping these acronyms whole
+///
+/// `#[gflags(require_docs)]` -- every generated flag must have a doc comment
+///
+/// `#[gflags(lenient)]` -- warn about unknown keywords instead of rejecting them
+///
+/// `#[gflags(export_help)]` -- generate a `<FLAG>_HELP` constant for each flag
+///
+/// `#[gflags(export_field_map)]` -- generate a `<STRUCT>_FLAG_TO_FIELD` constant
+///
+/// `#[gflags(group_header = "...")]` -- generate a `<STRUCT>_FLAG_GROUP` constant
+/// that groups the flags under this header
+///
+/// `#[gflags(unwrap = "...")]` -- unwrap this type like `Option<T>`
+///
+/// `#[gflags(transform = "...")]` -- transform field names before using them
+///
+/// `#[gflags(defaults(field = ..., ...))]` -- default values for these fields
+///
+/// `#[gflags(abbrev)]` -- also generate an abbreviated flag for every field
+///
+/// `#[gflags(both_cases)]` -- also generate every flag with the other separator
+///
+/// `#[gflags(generate_summary)]` -- generate a `flags_summary()` function
+///
+/// `#[gflags(generate_help_handler)]` -- generate a `handle_help()` function
+///
+/// `#[gflags(generate_clap)]` -- generate a `clap_command()` function (needs
+/// the `clap` feature)
+///
+/// `#[gflags(auto_env)]` -- read every flag from an environment variable
+///
+/// `#[gflags(env_prefix = "...")]` -- prefix environment variable names
+///
+/// `#[gflags(crate_path = "...")]` -- path to the `gflags` crate, if it is renamed
+///
+/// `#[gflags(generate_effective)]` -- generate an `effective_flags()` method
+///
+/// `#[gflags(generate_diff)]` -- generate a `non_default_flags()` method
+///
+/// `#[gflags(generate_builder)]` -- generate a `<STRUCT>FlagsBuilder` type (needs
+/// `generate_apply`)
+///
+/// `#[gflags(generate_value_map)]` -- generate a `flag_value_strings()` method
+///
+/// `#[gflags(generate_is_present)]` -- generate a `flag_is_present()` function
+///
+/// `#[gflags(generate_apply)]` -- generate an `apply_flags()` method
+///
+/// `#[gflags(generate_validate)]` -- generate a `validate()` method
+///
+/// `#[gflags(separator = "...")]` -- separate words in flag names with `-` or `_`
+///
+/// `#[gflags(prefix_separator = "...")]` -- separate the prefix from the field
+/// name with `-` or `_`
+///
+/// `#[gflags(word_separator = "...")]` -- separate the words of the field name
+/// with `-` or `_`
+///
+/// `#[gflags(feature = "...")]` -- only generate flags if this feature is enabled
+///
+/// `#[gflags(test_only)]` -- only generate flags when compiling tests
+///
+/// `#[gflags(only_pub)]` -- only generate flags for `pub` fields
+///
+/// `#[gflags(placeholder_shows_default)]` -- include default values in placeholders
+///
+/// `#[gflags(placeholder_from_name)]` -- use the last word of the flag's name as its placeholder
+///
+/// `#[gflags(skip_all)]` -- only generate flags for fields with `skip = false`
+///
+/// `#[gflags(respect_serde_flatten)]` -- skip fields with `#[serde(flatten)]`
+///
+/// `#[gflags(annotate_source)]` -- name the field each flag came from in its help
+///
+/// `#[gflags(wrap = ...)]` -- wrap help text lines at this many characters
+///
+/// `#[gflags(help_indent = ...)]` -- indent help text lines after the first by this many spaces
+///
 /// # Field level attributes
 ///
-/// `#[gflags(default = ...)]` -- default value for this flag
+/// `#[gflags(abbrev)]` -- also generate an abbreviated flag
+///
+/// `#[gflags(attr = "...")]` -- add these attributes to the flag's variable
+///
+/// `#[gflags(default = ...)]` -- default value for this flag, a literal or a `const`
+///
+/// `#[gflags(default_expr = "...")]` -- default value for this flag, as an expression
+///
+/// `#[gflags(default_if_empty = "...")]` -- value for `apply_flags()` to use if the field is empty
+///
+/// `#[gflags(delimiter = "...")]` -- split a `Vec<T>` field's flag on this delimiter
+///
+/// `#[gflags(choices = "...")]` -- only accept these comma separated values
+///
+/// `#[gflags(env = "...")]` -- read this flag from an environment variable
+///
+/// `#[gflags(from_str)]` -- parse the flag's value with its type's `FromStr`
+///
+/// `#[gflags(help_file = "...")]` -- append the contents of this file to the flag's help
+///
+/// `#[gflags(help_suffix = "...")]` -- append this text to the flag's help
+///
+/// `#[gflags(invert_help)]` -- say how to disable a `bool` flag with `default = true`
+///
+/// `#[gflags(name = "...")]` or `#[gflags(rename = "...")]` -- use this name
+/// instead of the field's name
+///
+/// `#[gflags(optional)]` -- treat this field as an `Option<T>` in `apply_flags()`
+///
+/// `#[gflags(parse_with = "...")]` -- parse the flag's value with this function
 ///
 /// `#[gflags(placeholder= "...")]` -- placeholder to display in help
 ///
+/// `#[gflags(required)]` -- `validate()` fails if this flag is not present
+///
+/// `#[gflags(single)]` -- `validate()` fails if this flag is given more than once
+///
+/// `#[gflags(min = ..., max = ...)]` -- `validate()` fails if the value is out of range
+///
+/// `#[gflags(min_values = ..., max_values = ...)]` -- `validate()` fails if a list flag has
+/// too few or too many values
+///
+/// `#[gflags(requires = "...")]` -- `validate()` fails if this flag is present
+/// without the flag for the named field
+///
+/// `#[gflags(conflicts_with = "...")]` -- `validate()` fails if this flag and
+/// the flag for the named field are both present
+///
+/// `#[gflags(secret)]` -- do not show this flag's value in the summary
+///
+/// `#[gflags(from_file)]` -- also generate a `--<name>-file` flag to read the value from
+///
 /// `#[gflags(skip)]` -- do not generate a flag for this field
 ///
+/// `#[gflags(skip = false)]` -- generate a flag for this field, overriding
+/// `skip_all` and `only_pub`
+///
 /// `#[gflags(type = "...")]` -- generate a flag with this type
 ///
-/// `#[gflags(visibility = "...")]` -- generate a flag with this visibility
+/// `#[gflags(visibility = "...")]` -- generate a flag with this visibility, or
+/// the field's visibility if `"inherit"`
+///
+/// On an `enum` a `bool` flag is generated for each unit variant, and field level
+/// attributes are not supported.
 ///
 /// Refer to the [crate level documentation](index.html) for a complete example.
 #[proc_macro_derive(GFlags, attributes(gflags))]
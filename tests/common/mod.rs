@@ -1,6 +1,9 @@
-use gflags;
+// Each test uses a different subset of these
+#![allow(dead_code)]
+
 use std::any::{Any, TypeId};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::process::{Command, Output};
 
 /// `ExpectedFlag` describes the expected state of a flag. Individual tests
 /// create one of these and pass to `check_flag` to confirm that the actual
@@ -26,6 +29,29 @@ pub fn fetch_flags() -> HashMap<&'static str, &'static gflags::registry::Flag> {
     flags
 }
 
+/// Asserts that no flag name has been registered more than once.
+///
+/// Flags from every `derive` in a test binary share one registry, so two
+/// structs that generate the same flag name both register it, and
+/// `fetch_flags` would silently keep only one of them. Give each struct a
+/// different `#[gflags(prefix = "...")]` to keep their flags apart.
+pub fn check_no_duplicate_flags() {
+    let mut seen: HashSet<&'static str> = HashSet::new();
+    let mut duplicates: Vec<&'static str> = vec![];
+
+    for flag in gflags::inventory::iter::<gflags::registry::Flag> {
+        if !seen.insert(flag.name) {
+            duplicates.push(flag.name);
+        }
+    }
+
+    assert!(
+        duplicates.is_empty(),
+        "Flags registered more than once: {:?}",
+        duplicates
+    );
+}
+
 /// Performs various assertions to confirm that the flag in `got` matches
 /// the expectations in `want`.
 pub fn check_flag<T: 'static>(
@@ -36,16 +62,14 @@ pub fn check_flag<T: 'static>(
         return;
     }
 
-    assert_eq!(
-        want.is_none() && got.is_some(),
-        false,
+    assert!(
+        !(want.is_none() && got.is_some()),
         "Unexpected flag with name --{}",
         got.unwrap().name
     );
 
-    assert_eq!(
-        want.is_some() && got.is_none(),
-        false,
+    assert!(
+        !(want.is_some() && got.is_none()),
         "Failed to find flag with name --{}",
         want.unwrap().name
     );
@@ -69,3 +93,41 @@ pub fn check_flag<T: 'static>(
 fn is_same_type<S: ?Sized + std::any::Any, T: ?Sized + std::any::Any>(_s: &S, _t: &T) -> bool {
     TypeId::of::<S>() == TypeId::of::<T>()
 }
+
+/// Set in the environment of the child process started by `run_child`.
+const CHILD: &str = "GFLAGS_DERIVE_TEST_CHILD";
+
+/// True if this is the child process started by `run_child`.
+///
+/// `gflags::parse()` reads the process's command line, so a test that needs
+/// flags on the command line runs itself as a child process with the flags
+/// to check. The test's `main` parses and checks the flags when this is true.
+pub fn is_child() -> bool {
+    std::env::var_os(CHILD).is_some()
+}
+
+/// Runs the test binary again as a child process with `args` on the command
+/// line, and returns its output.
+pub fn run_child(args: &[&str]) -> Output {
+    Command::new(std::env::current_exe().unwrap())
+        .args(args)
+        .env(CHILD, "1")
+        .output()
+        .unwrap()
+}
+
+/// Runs the child process with `args`, asserts that it succeeds, and returns
+/// what it printed to stdout.
+pub fn child_stdout(args: &[&str]) -> String {
+    let output = run_child(args);
+    assert!(output.status.success(), "{:?}", output);
+    String::from_utf8_lossy(&output.stdout).into_owned()
+}
+
+/// Runs the child process with `args`, asserts that it fails, and returns
+/// what it printed to stderr.
+pub fn child_stderr(args: &[&str]) -> String {
+    let output = run_child(args);
+    assert!(!output.status.success(), "{:?}", output);
+    String::from_utf8_lossy(&output.stderr).into_owned()
+}
@@ -0,0 +1,68 @@
+extern crate gflags_derive;
+use gflags_derive::GFlags;
+
+mod common;
+use common::*;
+
+#[derive(GFlags)]
+#[gflags(
+    prefix = "log-",
+    abbrev,
+    generate_apply,
+    generate_validate,
+    generate_is_present
+)]
+struct Config {
+    /// True if log messages should also be sent to STDERR
+    to_stderr: bool,
+
+    /// The directory to write log files to
+    #[gflags(default = "/tmp")]
+    dir: String,
+}
+
+// Runs itself as a child process with the flags to check, see `run_child`
+fn main() {
+    if is_child() {
+        gflags::parse();
+        let mut config = Config {
+            to_stderr: false,
+            dir: "/tmp".to_string(),
+        };
+        if let Err(errors) = config.validate() {
+            for error in errors {
+                eprintln!("{}", error);
+            }
+            std::process::exit(1);
+        }
+        config.apply_flags();
+        println!(
+            "{} {} {:?}",
+            config.to_stderr,
+            config.dir,
+            Config::flag_is_present("dir")
+        );
+        return;
+    }
+
+    // Nothing given, the fields keep their values
+    assert_eq!(child_stdout(&[]), "false /tmp Some(false)\n");
+
+    // The abbreviations set the same fields as the full flags
+    assert_eq!(
+        child_stdout(&["--lts", "--ld", "/var/log"]),
+        "true /var/log Some(true)\n"
+    );
+    assert_eq!(
+        child_stdout(&["--log-to-stderr", "--log-dir", "/var/log"]),
+        "true /var/log Some(true)\n"
+    );
+
+    // Giving both is an error
+    assert_eq!(
+        child_stderr(&["--ld", "/var/log", "--log-dir", "/srv/log"]),
+        "only one of --log-dir, --ld can be given\n"
+    );
+
+    println!("derive_with_abbrev_apply ... ok");
+}
@@ -0,0 +1,88 @@
+extern crate gflags_derive;
+use gflags_derive::GFlags;
+
+mod common;
+use common::*;
+
+#[test]
+fn derive_with_acronyms() {
+    #[derive(GFlags)]
+    #[gflags(prefix = "acr-", acronyms = "HTTPS, URL, OAuth")]
+    #[allow(dead_code, non_snake_case)]
+    struct Config {
+        /// The proxy to use
+        HTTPSProxyURL: String,
+
+        /// The token to authenticate with
+        getOAuthToken: String,
+
+        /// Snake case names are unchanged
+        url_id: u32,
+    }
+
+    #[derive(GFlags)]
+    #[gflags(prefix = "acr-mode-", acronyms = "OAuth")]
+    #[allow(dead_code)]
+    enum Mode {
+        /// Log in with OAuth
+        OAuthLogin,
+
+        /// Serve over HTTP
+        HTTPServer,
+    }
+
+    let mut flags = fetch_flags();
+
+    // The acronyms are kept as single words
+    check_flag(
+        Some(ExpectedFlag::<&str> {
+            doc: &["The proxy to use"],
+            name: "acr-https-proxy-url",
+            placeholder: None,
+            generated_flag: &ACR_HTTPS_PROXY_URL,
+        }),
+        flags.remove("acr-https-proxy-url"),
+    );
+
+    check_flag(
+        Some(ExpectedFlag::<&str> {
+            doc: &["The token to authenticate with"],
+            name: "acr-get-oauth-token",
+            placeholder: None,
+            generated_flag: &ACR_GET_OAUTH_TOKEN,
+        }),
+        flags.remove("acr-get-oauth-token"),
+    );
+
+    check_flag(
+        Some(ExpectedFlag::<u32> {
+            doc: &["Snake case names are unchanged"],
+            name: "acr-url-id",
+            placeholder: None,
+            generated_flag: &ACR_URL_ID,
+        }),
+        flags.remove("acr-url-id"),
+    );
+
+    // Variants use the acronyms too, and a run of capitals not in the list
+    // ends before the start of the next word
+    check_flag(
+        Some(ExpectedFlag::<bool> {
+            doc: &["Log in with OAuth"],
+            name: "acr-mode-oauth-login",
+            placeholder: None,
+            generated_flag: &ACR_MODE_OAUTH_LOGIN,
+        }),
+        flags.remove("acr-mode-oauth-login"),
+    );
+
+    check_flag(
+        Some(ExpectedFlag::<bool> {
+            doc: &["Serve over HTTP"],
+            name: "acr-mode-http-server",
+            placeholder: None,
+            generated_flag: &ACR_MODE_HTTP_SERVER,
+        }),
+        flags.remove("acr-mode-http-server"),
+    );
+}
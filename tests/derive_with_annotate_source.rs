@@ -0,0 +1,43 @@
+extern crate gflags_derive;
+use gflags_derive::GFlags;
+
+mod common;
+use common::*;
+
+#[test]
+fn derive_with_annotate_source() {
+    #[derive(GFlags)]
+    #[gflags(prefix = "log-", annotate_source)]
+    #[allow(dead_code)]
+    struct Config {
+        /// True if log messages should also be sent to STDERR
+        to_stderr: bool,
+
+        dir: String,
+    }
+
+    let mut flags = fetch_flags();
+
+    check_flag(
+        Some(ExpectedFlag::<bool> {
+            doc: &[
+                "True if log messages should also be sent to STDERR",
+                "Generated from Config::to_stderr",
+            ],
+            name: "log-to-stderr",
+            placeholder: None,
+            generated_flag: &LOG_TO_STDERR,
+        }),
+        flags.remove("log-to-stderr"),
+    );
+
+    check_flag(
+        Some(ExpectedFlag::<&str> {
+            doc: &["Generated from Config::dir"],
+            name: "log-dir",
+            placeholder: None,
+            generated_flag: &LOG_DIR,
+        }),
+        flags.remove("log-dir"),
+    );
+}
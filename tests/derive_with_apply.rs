@@ -0,0 +1,54 @@
+extern crate gflags_derive;
+use gflags_derive::GFlags;
+
+mod common;
+use common::*;
+use std::path::PathBuf;
+
+#[test]
+fn derive_with_apply() {
+    #[derive(GFlags)]
+    #[gflags(prefix = "apply-", generate_apply)]
+    struct Config {
+        /// True if log messages should also be sent to STDERR
+        to_stderr: Option<bool>,
+
+        /// True if log messages should be compressed
+        compress: Option<bool>,
+
+        /// The directory to write log files to
+        #[gflags(type = "&str", default = "/tmp")]
+        dir: PathBuf,
+
+        /// Maximum size of a log file, in KiB
+        max_size: u32,
+    }
+
+    let mut flags = fetch_flags();
+
+    check_flag(
+        Some(ExpectedFlag::<bool> {
+            doc: &["True if log messages should also be sent to STDERR"],
+            name: "apply-to-stderr",
+            placeholder: None,
+            generated_flag: &APPLY_TO_STDERR,
+        }),
+        flags.remove("apply-to-stderr"),
+    );
+
+    let mut config = Config {
+        to_stderr: None,
+        compress: Some(false),
+        dir: PathBuf::from("/var/log"),
+        max_size: 1024,
+    };
+
+    // No flags are present, so nothing changes. In particular, `None` is
+    // not replaced with the `false` of a missing `bool` flag
+    config.apply_flags();
+
+    assert_eq!(config.to_stderr, None);
+    assert_eq!(config.compress, Some(false));
+    assert_eq!(config.dir, PathBuf::from("/var/log"));
+    assert_eq!(config.max_size, 1024);
+}
@@ -0,0 +1,40 @@
+extern crate gflags_derive;
+use gflags_derive::GFlags;
+
+mod common;
+use common::*;
+use std::path::PathBuf;
+
+trait Setting {
+    type Flag;
+}
+
+struct Dir;
+
+impl Setting for Dir {
+    type Flag = &'static str;
+}
+
+#[test]
+fn derive_with_associated_type() {
+    #[derive(GFlags)]
+    #[allow(dead_code)]
+    struct Config {
+        /// The directory to write log files to
+        #[gflags(type = "<Dir as Setting>::Flag")]
+        dir: PathBuf,
+    }
+
+    let mut flags = fetch_flags();
+
+    // The flag should be an `&str`, the type of `<Dir as Setting>::Flag`
+    check_flag(
+        Some(ExpectedFlag::<&str> {
+            doc: &["The directory to write log files to"],
+            name: "dir",
+            placeholder: None,
+            generated_flag: &DIR,
+        }),
+        flags.remove("dir"),
+    );
+}
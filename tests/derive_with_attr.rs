@@ -0,0 +1,66 @@
+extern crate gflags_derive;
+use gflags_derive::GFlags;
+
+mod common;
+use common::*;
+
+#[test]
+fn derive_with_attr() {
+    #[derive(GFlags)]
+    #[gflags(prefix = "attr-")]
+    #[allow(dead_code)]
+    struct Config {
+        /// True if log messages should also be sent to STDERR
+        #[gflags(attr = "#[doc(hidden)]", default = true)]
+        to_stderr: bool,
+
+        /// The directory to write log files to
+        #[gflags(placeholder = "DIR", default = "/tmp")]
+        #[gflags(attr = "#[doc(hidden)]")]
+        #[gflags(attr = "#[cfg_attr(all(), allow(dead_code))]")]
+        dir: String,
+
+        /// Number of log files to keep
+        #[gflags(attr = "#[doc(hidden)] #[allow(dead_code)]")]
+        count: u32,
+    }
+
+    let mut flags = fetch_flags();
+
+    // The flags should be registered the same way `gflags::define!` does
+    check_flag(
+        Some(ExpectedFlag::<bool> {
+            doc: &["True if log messages should also be sent to STDERR"],
+            name: "attr-to-stderr",
+            placeholder: None,
+            generated_flag: &ATTR_TO_STDERR,
+        }),
+        flags.remove("attr-to-stderr"),
+    );
+
+    assert!(ATTR_TO_STDERR.flag);
+
+    check_flag(
+        Some(ExpectedFlag::<&str> {
+            doc: &["The directory to write log files to"],
+            name: "attr-dir",
+            placeholder: Some("DIR"),
+            generated_flag: &ATTR_DIR,
+        }),
+        flags.remove("attr-dir"),
+    );
+
+    assert_eq!(ATTR_DIR.flag, "/tmp");
+
+    check_flag(
+        Some(ExpectedFlag::<u32> {
+            doc: &["Number of log files to keep"],
+            name: "attr-count",
+            placeholder: None,
+            generated_flag: &ATTR_COUNT,
+        }),
+        flags.remove("attr-count"),
+    );
+
+    assert!(!ATTR_COUNT.is_present());
+}
@@ -0,0 +1,63 @@
+extern crate gflags_derive;
+use gflags_derive::GFlags;
+
+mod common;
+use common::*;
+
+#[test]
+fn derive_with_both_cases() {
+    #[derive(GFlags)]
+    #[gflags(prefix = "both-", both_cases)]
+    #[allow(dead_code)]
+    struct Config {
+        /// True if log messages should also be sent to STDERR
+        to_stderr: bool,
+
+        /// The directory to write log files to
+        #[gflags(placeholder = "DIR")]
+        dir: String,
+    }
+
+    let mut flags = fetch_flags();
+
+    check_flag(
+        Some(ExpectedFlag::<bool> {
+            doc: &["True if log messages should also be sent to STDERR"],
+            name: "both-to-stderr",
+            placeholder: None,
+            generated_flag: &BOTH_TO_STDERR,
+        }),
+        flags.remove("both-to-stderr"),
+    );
+
+    // The other form has the same help, and a variable with a suffix
+    check_flag(
+        Some(ExpectedFlag::<bool> {
+            doc: &["True if log messages should also be sent to STDERR"],
+            name: "both_to_stderr",
+            placeholder: None,
+            generated_flag: &BOTH_TO_STDERR_SNAKE,
+        }),
+        flags.remove("both_to_stderr"),
+    );
+
+    check_flag(
+        Some(ExpectedFlag::<&str> {
+            doc: &["The directory to write log files to"],
+            name: "both-dir",
+            placeholder: Some("DIR"),
+            generated_flag: &BOTH_DIR,
+        }),
+        flags.remove("both-dir"),
+    );
+
+    check_flag(
+        Some(ExpectedFlag::<&str> {
+            doc: &["The directory to write log files to"],
+            name: "both_dir",
+            placeholder: Some("DIR"),
+            generated_flag: &BOTH_DIR_SNAKE,
+        }),
+        flags.remove("both_dir"),
+    );
+}
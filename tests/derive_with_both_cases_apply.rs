@@ -0,0 +1,70 @@
+extern crate gflags_derive;
+use gflags_derive::GFlags;
+
+mod common;
+use common::*;
+
+#[derive(GFlags)]
+#[gflags(
+    prefix = "log-",
+    both_cases,
+    generate_apply,
+    generate_validate,
+    generate_summary,
+    generate_diff,
+    generate_is_present
+)]
+struct Config {
+    /// The directory to write log files to
+    #[gflags(default = "/tmp")]
+    dir: String,
+}
+
+// Runs itself as a child process with the flags to check, see `run_child`
+fn main() {
+    if is_child() {
+        gflags::parse();
+        let mut config = Config {
+            dir: "/tmp".to_string(),
+        };
+        if let Err(errors) = config.validate() {
+            for error in errors {
+                eprintln!("{}", error);
+            }
+            std::process::exit(1);
+        }
+        config.apply_flags();
+        print!("{}", Config::flags_summary());
+        println!(
+            "{} {:?} {:?}",
+            config.dir,
+            config.non_default_flags(),
+            Config::flag_is_present("dir")
+        );
+        return;
+    }
+
+    // Nothing given, the field keeps its value
+    assert_eq!(
+        child_stdout(&[]),
+        "--log-dir: \"/tmp\"\n/tmp [] Some(false)\n"
+    );
+
+    // The flag with the other separator sets the same field
+    assert_eq!(
+        child_stdout(&["--log_dir", "/var/log"]),
+        "--log-dir: \"/var/log\"\n/var/log [\"log-dir\"] Some(true)\n"
+    );
+    assert_eq!(
+        child_stdout(&["--log-dir", "/var/log"]),
+        "--log-dir: \"/var/log\"\n/var/log [\"log-dir\"] Some(true)\n"
+    );
+
+    // Giving both is an error
+    assert_eq!(
+        child_stderr(&["--log_dir", "/var/log", "--log-dir", "/srv/log"]),
+        "only one of --log-dir, --log_dir can be given\n"
+    );
+
+    println!("derive_with_both_cases_apply ... ok");
+}
@@ -0,0 +1,76 @@
+extern crate gflags_derive;
+use gflags_derive::GFlags;
+
+mod common;
+use common::*;
+
+trait Shape {
+    fn sides(&self) -> u32;
+}
+
+struct Triangle;
+
+impl Shape for Triangle {
+    fn sides(&self) -> u32 {
+        3
+    }
+}
+
+struct Square;
+
+impl Shape for Square {
+    fn sides(&self) -> u32 {
+        4
+    }
+}
+
+fn parse_shape(s: &str) -> Result<Box<dyn Shape>, String> {
+    match s {
+        "triangle" => Ok(Box::new(Triangle)),
+        "square" => Ok(Box::new(Square)),
+        _ => Err("expected triangle or square".to_string()),
+    }
+}
+
+#[test]
+fn derive_with_boxed_trait() {
+    #[derive(GFlags)]
+    #[gflags(prefix = "boxed-", generate_apply)]
+    struct Config {
+        /// The shape to draw
+        #[gflags(type = "&str", parse_with = "parse_shape", env = "BOXED_SHAPE")]
+        shape: Box<dyn Shape>,
+
+        /// The shape to draw next
+        #[gflags(type = "&str", parse_with = "parse_shape")]
+        next: Option<Box<dyn Shape>>,
+    }
+
+    let mut flags = fetch_flags();
+
+    // The flag holds the string, not the boxed value
+    check_flag(
+        Some(ExpectedFlag::<&str> {
+            doc: &["The shape to draw"],
+            name: "boxed-shape",
+            placeholder: None,
+            generated_flag: &BOXED_SHAPE,
+        }),
+        flags.remove("boxed-shape"),
+    );
+
+    let mut config = Config {
+        shape: Box::new(Square),
+        next: None,
+    };
+
+    // The flags are not on the command line, so the fields are unchanged
+    config.apply_flags();
+    assert_eq!(config.shape.sides(), 4);
+    assert!(config.next.is_none());
+
+    // `apply_flags()` parses the value into the boxed trait object
+    std::env::set_var("BOXED_SHAPE", "triangle");
+    config.apply_flags();
+    assert_eq!(config.shape.sides(), 3);
+}
@@ -0,0 +1,53 @@
+extern crate gflags_derive;
+use gflags_derive::GFlags;
+
+mod common;
+use common::*;
+
+#[test]
+fn derive_with_builder() {
+    #[derive(Debug, Default, PartialEq, GFlags)]
+    #[gflags(prefix = "builder-", generate_apply, generate_builder)]
+    struct Config {
+        /// The directory to write log files to
+        dir: String,
+
+        /// The user to write log files as
+        #[gflags(env = "BUILDER_USER")]
+        user: String,
+
+        /// Maximum size of a log file, in KiB
+        max_size: u32,
+    }
+
+    let mut flags = fetch_flags();
+
+    check_flag(
+        Some(ExpectedFlag::<&str> {
+            doc: &["The user to write log files as"],
+            name: "builder-user",
+            placeholder: None,
+            generated_flag: &BUILDER_USER,
+        }),
+        flags.remove("builder-user"),
+    );
+
+    // The flags are not on the command line, so set `user` in the
+    // environment. `build()` applies it with `apply_flags()`, over the value
+    // set in code
+    std::env::set_var("BUILDER_USER", "logger");
+
+    let config = ConfigFlagsBuilder::new()
+        .dir("/var/log".to_string())
+        .user("root".to_string())
+        .build();
+
+    assert_eq!(
+        config,
+        Config {
+            dir: "/var/log".to_string(),
+            user: "logger".to_string(),
+            max_size: 0,
+        }
+    );
+}
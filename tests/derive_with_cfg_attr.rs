@@ -0,0 +1,40 @@
+extern crate gflags_derive;
+use gflags_derive::GFlags;
+
+mod common;
+use common::*;
+
+#[test]
+fn derive_with_cfg_attr() {
+    // `all()` is always true, so this behaves like an enabled feature
+    #[cfg_attr(all(), derive(GFlags))]
+    #[allow(dead_code)]
+    struct Config {
+        /// True if log messages should also be sent to STDERR
+        to_stderr: bool,
+    }
+
+    // `any()` is always false, so this behaves like a disabled feature
+    #[cfg_attr(any(), derive(GFlags))]
+    #[cfg_attr(any(), gflags(prefix = "disabled-"))]
+    #[allow(dead_code)]
+    struct DisabledConfig {
+        /// The directory to write log files to
+        dir: String,
+    }
+
+    let mut flags = fetch_flags();
+
+    check_flag(
+        Some(ExpectedFlag::<bool> {
+            doc: &["True if log messages should also be sent to STDERR"],
+            name: "to-stderr",
+            placeholder: None,
+            generated_flag: &TO_STDERR,
+        }),
+        flags.remove("to-stderr"),
+    );
+
+    check_flag::<&str>(None, flags.remove("dir"));
+    check_flag::<&str>(None, flags.remove("disabled-dir"));
+}
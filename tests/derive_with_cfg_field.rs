@@ -0,0 +1,76 @@
+extern crate gflags_derive;
+use gflags_derive::GFlags;
+
+mod common;
+use common::*;
+
+#[test]
+fn derive_with_cfg_field() {
+    // `test` is set when running tests, so `to_stderr` behaves like a field
+    // behind an enabled feature, and `dir` like a field behind a disabled
+    // feature. The generated code must compile in both cases.
+    #[derive(GFlags)]
+    #[gflags(
+        prefix = "cfg-field-",
+        generate_apply,
+        generate_validate,
+        generate_summary,
+        export_field_map
+    )]
+    struct Config {
+        /// True if log messages should also be sent to STDERR
+        #[cfg(test)]
+        #[gflags(default = true)]
+        to_stderr: bool,
+
+        /// The directory to write log files to
+        #[cfg(not(test))]
+        #[gflags(required)]
+        dir: String,
+
+        /// Maximum size of a log file, in KiB
+        #[gflags(default = 1024)]
+        max_size: u32,
+    }
+
+    let mut flags = fetch_flags();
+
+    check_flag(
+        Some(ExpectedFlag::<bool> {
+            doc: &["True if log messages should also be sent to STDERR"],
+            name: "cfg-field-to-stderr",
+            placeholder: None,
+            generated_flag: &CFG_FIELD_TO_STDERR,
+        }),
+        flags.remove("cfg-field-to-stderr"),
+    );
+
+    // No flag is defined for the disabled field
+    check_flag::<&str>(None, flags.remove("cfg-field-dir"));
+
+    let mut config = Config {
+        to_stderr: false,
+        max_size: 0,
+    };
+    config.apply_flags();
+
+    // No flags are present, so nothing changes
+    assert!(!config.to_stderr);
+    assert_eq!(config.max_size, 0);
+
+    // The disabled field's `required` check is gated too
+    assert_eq!(config.validate(), Ok(()));
+
+    assert_eq!(
+        Config::flags_summary(),
+        "--cfg-field-to-stderr: true\n--cfg-field-max-size: 1024\n"
+    );
+
+    assert_eq!(
+        CONFIG_FLAG_TO_FIELD,
+        &[
+            ("cfg-field-to-stderr", "to_stderr"),
+            ("cfg-field-max-size", "max_size")
+        ]
+    );
+}
@@ -0,0 +1,70 @@
+extern crate gflags_derive;
+use gflags_derive::GFlags;
+
+mod common;
+use common::*;
+
+#[test]
+fn derive_with_choices() {
+    #[derive(GFlags)]
+    #[gflags(prefix = "choice-", generate_apply)]
+    struct Config {
+        /// Minimum level of messages to log
+        #[gflags(choices = "error, warn, info", default = "info")]
+        level: String,
+
+        /// Format of log messages
+        #[gflags(choices = "text,json", env = "CHOICE_FORMAT")]
+        format: Option<String>,
+    }
+
+    let mut flags = fetch_flags();
+
+    // The choices are listed in the help
+    check_flag(
+        Some(ExpectedFlag::<ChoiceLevelChoice> {
+            doc: &[
+                "Minimum level of messages to log",
+                "[choices: error, warn, info]",
+            ],
+            name: "choice-level",
+            placeholder: None,
+            generated_flag: &CHOICE_LEVEL,
+        }),
+        flags.remove("choice-level"),
+    );
+
+    check_flag(
+        Some(ExpectedFlag::<ChoiceFormatChoice> {
+            doc: &["Format of log messages", "[choices: text, json]"],
+            name: "choice-format",
+            placeholder: None,
+            generated_flag: &CHOICE_FORMAT,
+        }),
+        flags.remove("choice-format"),
+    );
+
+    assert_eq!(CHOICE_LEVEL.flag, ChoiceLevelChoice("info"));
+    assert_eq!(CHOICE_LEVEL.flag.to_string(), "info");
+
+    // Only the choices parse
+    assert_eq!(
+        "warn".parse::<ChoiceLevelChoice>(),
+        Ok(ChoiceLevelChoice("warn"))
+    );
+    assert_eq!(
+        "debug".parse::<ChoiceLevelChoice>(),
+        Err("expected one of: error, warn, info".to_string())
+    );
+    assert!("JSON".parse::<ChoiceFormatChoice>().is_err());
+
+    // The value from the environment is also parsed, and copied to the field
+    std::env::set_var("CHOICE_FORMAT", "json");
+    let mut config = Config {
+        level: "error".to_string(),
+        format: None,
+    };
+    config.apply_flags();
+    assert_eq!(config.level, "error");
+    assert_eq!(config.format, Some("json".to_string()));
+}
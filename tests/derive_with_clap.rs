@@ -0,0 +1,72 @@
+#![cfg(feature = "clap")]
+
+extern crate gflags_derive;
+use gflags_derive::GFlags;
+
+mod common;
+use common::*;
+
+#[test]
+fn derive_with_clap() {
+    #[derive(GFlags)]
+    #[gflags(prefix = "clap-", generate_clap)]
+    #[allow(dead_code)]
+    struct Config {
+        /// True if log messages should also be sent to STDERR
+        to_stderr: bool,
+
+        /// The directory to write log files to
+        #[gflags(default = "/tmp", placeholder = "DIR")]
+        dir: String,
+
+        /// Maximum size of a log file
+        #[gflags(default = 100u64)]
+        max_size: u64,
+    }
+
+    let command = Config::clap_command();
+
+    let names: Vec<&str> = command
+        .get_arguments()
+        .map(|arg| arg.get_id().as_str())
+        .collect();
+    assert_eq!(names, vec!["clap-to-stderr", "clap-dir", "clap-max-size"]);
+
+    let dir = command
+        .get_arguments()
+        .find(|arg| arg.get_id() == "clap-dir")
+        .unwrap();
+    assert_eq!(dir.get_long(), Some("clap-dir"));
+    assert_eq!(
+        dir.get_help().map(ToString::to_string),
+        Some("The directory to write log files to".to_string())
+    );
+    assert_eq!(dir.get_default_values(), &["/tmp"]);
+
+    let matches = command
+        .try_get_matches_from(vec!["app", "--clap-to-stderr", "--clap-dir", "/var/log"])
+        .unwrap();
+    assert!(matches.get_flag("clap-to-stderr"));
+    assert_eq!(
+        matches.get_one::<String>("clap-dir").map(String::as_str),
+        Some("/var/log")
+    );
+    assert_eq!(
+        matches
+            .get_one::<String>("clap-max-size")
+            .map(String::as_str),
+        Some("100")
+    );
+
+    let mut flags = fetch_flags();
+
+    check_flag(
+        Some(ExpectedFlag::<&str> {
+            doc: &["The directory to write log files to"],
+            name: "clap-dir",
+            placeholder: Some("DIR"),
+            generated_flag: &CLAP_DIR,
+        }),
+        flags.remove("clap-dir"),
+    );
+}
@@ -0,0 +1,50 @@
+extern crate gflags_derive;
+use gflags_derive::GFlags;
+
+mod common;
+use common::*;
+
+#[test]
+fn derive_with_conflicts_with() {
+    #[derive(GFlags)]
+    #[gflags(prefix = "mode-", generate_validate)]
+    struct Config {
+        /// Only log errors
+        #[gflags(conflicts_with = "verbose")]
+        quiet: bool,
+
+        /// Log everything
+        #[gflags(conflicts_with = "quiet")]
+        verbose: bool,
+    }
+
+    let mut flags = fetch_flags();
+
+    check_flag(
+        Some(ExpectedFlag::<bool> {
+            doc: &["Only log errors"],
+            name: "mode-quiet",
+            placeholder: None,
+            generated_flag: &MODE_QUIET,
+        }),
+        flags.remove("mode-quiet"),
+    );
+
+    check_flag(
+        Some(ExpectedFlag::<bool> {
+            doc: &["Log everything"],
+            name: "mode-verbose",
+            placeholder: None,
+            generated_flag: &MODE_VERBOSE,
+        }),
+        flags.remove("mode-verbose"),
+    );
+
+    let config = Config {
+        quiet: false,
+        verbose: false,
+    };
+
+    // Neither flag is present on the command line, so they do not conflict
+    assert_eq!(config.validate(), Ok(()));
+}
@@ -0,0 +1,49 @@
+extern crate gflags_derive;
+use gflags_derive::GFlags;
+
+mod common;
+use common::*;
+
+const DEFAULT_MAX_SIZE: u32 = 64 * 1024;
+
+#[test]
+fn derive_with_const_default() {
+    #[derive(GFlags)]
+    #[allow(dead_code)]
+    #[gflags(prefix = "const-")]
+    struct Config {
+        /// Maximum size of a log file, in KiB
+        #[gflags(default = DEFAULT_MAX_SIZE)]
+        max_size: u32,
+
+        /// Offset to apply to timestamps
+        #[gflags(default = -1)]
+        offset: i32,
+    }
+
+    let mut flags = fetch_flags();
+
+    check_flag(
+        Some(ExpectedFlag::<u32> {
+            doc: &["Maximum size of a log file, in KiB"],
+            name: "const-max-size",
+            placeholder: None,
+            generated_flag: &CONST_MAX_SIZE,
+        }),
+        flags.remove("const-max-size"),
+    );
+
+    assert_eq!(CONST_MAX_SIZE.flag, DEFAULT_MAX_SIZE);
+
+    check_flag(
+        Some(ExpectedFlag::<i32> {
+            doc: &["Offset to apply to timestamps"],
+            name: "const-offset",
+            placeholder: None,
+            generated_flag: &CONST_OFFSET,
+        }),
+        flags.remove("const-offset"),
+    );
+
+    assert_eq!(CONST_OFFSET.flag, -1);
+}
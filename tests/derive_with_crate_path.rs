@@ -0,0 +1,47 @@
+extern crate gflags_derive;
+use gflags_derive::GFlags;
+
+mod common;
+use common::*;
+
+// Re-export `gflags` under a different name, as a crate that wraps it might
+mod my_gflags {
+    pub use gflags::*;
+}
+
+#[test]
+fn derive_with_crate_path() {
+    #[derive(GFlags)]
+    #[gflags(prefix = "path-", crate_path = "my_gflags")]
+    #[allow(dead_code)]
+    struct Config {
+        /// True if log messages should also be sent to STDERR
+        to_stderr: bool,
+
+        /// The directory to write log files to
+        #[gflags(default = "/tmp")]
+        dir: String,
+    }
+
+    let mut flags = fetch_flags();
+
+    check_flag(
+        Some(ExpectedFlag::<bool> {
+            doc: &["True if log messages should also be sent to STDERR"],
+            name: "path-to-stderr",
+            placeholder: None,
+            generated_flag: &PATH_TO_STDERR,
+        }),
+        flags.remove("path-to-stderr"),
+    );
+
+    check_flag(
+        Some(ExpectedFlag::<&str> {
+            doc: &["The directory to write log files to"],
+            name: "path-dir",
+            placeholder: None,
+            generated_flag: &PATH_DIR,
+        }),
+        flags.remove("path-dir"),
+    );
+}
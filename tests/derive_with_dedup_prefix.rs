@@ -0,0 +1,69 @@
+extern crate gflags_derive;
+use gflags_derive::GFlags;
+
+mod common;
+use common::*;
+
+#[test]
+fn derive_with_dedup_prefix() {
+    #[derive(GFlags)]
+    #[gflags(prefix = "log-", dedup_prefix)]
+    #[allow(dead_code)]
+    struct Config {
+        /// The directory to write log files to
+        log_dir: String,
+
+        /// True if log messages should also be sent to STDERR
+        to_stderr: bool,
+
+        /// Only the prefix, so nothing is removed
+        log: bool,
+
+        /// The prefix is not a whole word, so nothing is removed
+        logger: String,
+    }
+
+    let mut flags = fetch_flags();
+
+    check_flag(
+        Some(ExpectedFlag::<&str> {
+            doc: &["The directory to write log files to"],
+            name: "log-dir",
+            placeholder: None,
+            generated_flag: &LOG_DIR,
+        }),
+        flags.remove("log-dir"),
+    );
+
+    check_flag(
+        Some(ExpectedFlag::<bool> {
+            doc: &["True if log messages should also be sent to STDERR"],
+            name: "log-to-stderr",
+            placeholder: None,
+            generated_flag: &LOG_TO_STDERR,
+        }),
+        flags.remove("log-to-stderr"),
+    );
+
+    check_flag(
+        Some(ExpectedFlag::<bool> {
+            doc: &["Only the prefix, so nothing is removed"],
+            name: "log-log",
+            placeholder: None,
+            generated_flag: &LOG_LOG,
+        }),
+        flags.remove("log-log"),
+    );
+
+    check_flag(
+        Some(ExpectedFlag::<&str> {
+            doc: &["The prefix is not a whole word, so nothing is removed"],
+            name: "log-logger",
+            placeholder: None,
+            generated_flag: &LOG_LOGGER,
+        }),
+        flags.remove("log-logger"),
+    );
+
+    check_flag::<&str>(None, flags.remove("log-log-dir"));
+}
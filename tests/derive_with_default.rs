@@ -1,5 +1,4 @@
 extern crate gflags_derive;
-use gflags;
 use gflags_derive::GFlags;
 
 mod common;
@@ -33,10 +32,7 @@ fn derive_with_default() {
         flags.remove("to-stderr"),
     );
 
-    assert_eq!(
-        TO_STDERR.flag, true,
-        "TO_STDERR default value should be `true`"
-    );
+    assert!(TO_STDERR.flag, "TO_STDERR default value should be `true`");
 
     check_flag(
         Some(ExpectedFlag::<&str> {
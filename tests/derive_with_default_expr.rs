@@ -0,0 +1,82 @@
+extern crate gflags_derive;
+use gflags_derive::GFlags;
+
+mod common;
+use common::*;
+
+#[test]
+fn derive_with_default_expr() {
+    #[derive(GFlags)]
+    #[allow(dead_code)]
+    struct Config {
+        /// The directory to write log files to
+        #[gflags(default_expr = r#"if cfg!(windows) { "C:\\tmp" } else { "/tmp" }"#)]
+        dir: String,
+
+        /// Maximum size of a log file, in KiB
+        #[gflags(default_expr = "64 * 1024")]
+        max_size: u32,
+
+        /// True if log messages should be verbose
+        #[gflags(default_expr = "cfg!(debug_assertions)")]
+        verbose: bool,
+
+        /// True if log messages should be compressed
+        #[gflags(default_expr = "!cfg!(debug_assertions) || cfg!(test)")]
+        compress: bool,
+    }
+
+    let mut flags = fetch_flags();
+
+    check_flag(
+        Some(ExpectedFlag::<&str> {
+            doc: &["The directory to write log files to"],
+            name: "dir",
+            placeholder: None,
+            generated_flag: &DIR,
+        }),
+        flags.remove("dir"),
+    );
+
+    let want = if cfg!(windows) { "C:\\tmp" } else { "/tmp" };
+    assert_eq!(DIR.flag, want, "DIR default value should be `{}`", want);
+
+    check_flag(
+        Some(ExpectedFlag::<u32> {
+            doc: &["Maximum size of a log file, in KiB"],
+            name: "max-size",
+            placeholder: None,
+            generated_flag: &MAX_SIZE,
+        }),
+        flags.remove("max-size"),
+    );
+
+    assert_eq!(
+        MAX_SIZE.flag, 65536,
+        "MAX_SIZE default value should be 65536"
+    );
+
+    check_flag(
+        Some(ExpectedFlag::<bool> {
+            doc: &["True if log messages should be verbose"],
+            name: "verbose",
+            placeholder: None,
+            generated_flag: &VERBOSE,
+        }),
+        flags.remove("verbose"),
+    );
+
+    assert_eq!(VERBOSE.flag, cfg!(debug_assertions));
+
+    check_flag(
+        Some(ExpectedFlag::<bool> {
+            doc: &["True if log messages should be compressed"],
+            name: "compress",
+            placeholder: None,
+            generated_flag: &COMPRESS,
+        }),
+        flags.remove("compress"),
+    );
+
+    assert_eq!(COMPRESS.flag, !cfg!(debug_assertions) || cfg!(test));
+}
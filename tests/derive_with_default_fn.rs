@@ -0,0 +1,70 @@
+extern crate gflags_derive;
+use gflags_derive::GFlags;
+
+mod common;
+use common::*;
+
+const fn default_max_size() -> u32 {
+    64 * 1024
+}
+
+const fn default_dir() -> &'static str {
+    if cfg!(windows) {
+        "C:\\logs"
+    } else {
+        "/var/log"
+    }
+}
+
+mod defaults {
+    pub const fn retries(attempts: u32) -> u32 {
+        attempts * 2
+    }
+}
+
+#[test]
+fn derive_with_default_fn() {
+    #[derive(GFlags)]
+    #[gflags(prefix = "dfn-")]
+    #[allow(dead_code)]
+    struct Config {
+        /// Maximum size of a log file, in KiB
+        #[gflags(default_expr = "default_max_size()")]
+        max_size: u32,
+
+        /// The directory to write log files to
+        #[gflags(default_expr = "default_dir()")]
+        dir: String,
+
+        /// Number of times to retry writing a log message
+        #[gflags(default_expr = "defaults::retries(3)")]
+        retries: u32,
+    }
+
+    let mut flags = fetch_flags();
+
+    check_flag(
+        Some(ExpectedFlag::<u32> {
+            doc: &["Maximum size of a log file, in KiB"],
+            name: "dfn-max-size",
+            placeholder: None,
+            generated_flag: &DFN_MAX_SIZE,
+        }),
+        flags.remove("dfn-max-size"),
+    );
+
+    check_flag(
+        Some(ExpectedFlag::<&str> {
+            doc: &["The directory to write log files to"],
+            name: "dfn-dir",
+            placeholder: None,
+            generated_flag: &DFN_DIR,
+        }),
+        flags.remove("dfn-dir"),
+    );
+
+    // The functions are called when the program is compiled
+    assert_eq!(DFN_MAX_SIZE.flag, 65536);
+    assert_eq!(DFN_DIR.flag, default_dir());
+    assert_eq!(DFN_RETRIES.flag, 6);
+}
@@ -0,0 +1,52 @@
+extern crate gflags_derive;
+use gflags_derive::GFlags;
+
+mod common;
+use common::*;
+
+#[test]
+fn derive_with_default_if_empty() {
+    #[derive(GFlags)]
+    #[gflags(prefix = "empty-", generate_apply)]
+    struct Config {
+        /// String to use for password characters
+        #[gflags(default_if_empty = "ABCDEF")]
+        charset: String,
+
+        /// Characters that must not be used
+        #[gflags(default_if_empty = "O0")]
+        exclude: Option<String>,
+    }
+
+    let mut flags = fetch_flags();
+
+    check_flag(
+        Some(ExpectedFlag::<&str> {
+            doc: &["String to use for password characters"],
+            name: "empty-charset",
+            placeholder: None,
+            generated_flag: &EMPTY_CHARSET,
+        }),
+        flags.remove("empty-charset"),
+    );
+
+    // Empty values fall back to the default
+    let mut config = Config {
+        charset: "".to_string(),
+        exclude: Some("".to_string()),
+    };
+    config.apply_flags();
+
+    assert_eq!(config.charset, "ABCDEF");
+    assert_eq!(config.exclude, Some("O0".to_string()));
+
+    // Other values, including `None`, are left alone
+    let mut config = Config {
+        charset: "XYZ".to_string(),
+        exclude: None,
+    };
+    config.apply_flags();
+
+    assert_eq!(config.charset, "XYZ");
+    assert_eq!(config.exclude, None);
+}
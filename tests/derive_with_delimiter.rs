@@ -0,0 +1,75 @@
+extern crate gflags_derive;
+use gflags_derive::GFlags;
+
+mod common;
+use common::*;
+
+#[test]
+fn derive_with_delimiter() {
+    #[derive(GFlags)]
+    #[gflags(prefix = "list-", generate_apply, auto_env)]
+    struct Config {
+        /// Tags to add to each log line
+        #[gflags(delimiter = ",", placeholder = "TAGS")]
+        tags: Vec<String>,
+
+        /// Ports to listen on
+        #[gflags(delimiter = ":")]
+        ports: Option<Vec<u16>>,
+
+        /// Hosts to connect to
+        #[gflags(delimiter = ",")]
+        hosts: Vec<String>,
+    }
+
+    let mut flags = fetch_flags();
+
+    // `Vec<String>` should have been converted to the generated list type
+    check_flag(
+        Some(ExpectedFlag::<ListTagsList> {
+            doc: &["Tags to add to each log line"],
+            name: "list-tags",
+            placeholder: Some("TAGS"),
+            generated_flag: &LIST_TAGS,
+        }),
+        flags.remove("list-tags"),
+    );
+
+    check_flag(
+        Some(ExpectedFlag::<ListPortsList> {
+            doc: &["Ports to listen on"],
+            name: "list-ports",
+            placeholder: None,
+            generated_flag: &LIST_PORTS,
+        }),
+        flags.remove("list-ports"),
+    );
+
+    // The list type splits the value on the delimiter
+    assert_eq!(
+        "a,b,,c".parse::<ListTagsList>(),
+        Ok(ListTagsList(vec![
+            "a".to_string(),
+            "b".to_string(),
+            "".to_string(),
+            "c".to_string()
+        ]))
+    );
+    assert_eq!("".parse::<ListTagsList>(), Ok(ListTagsList(vec![])));
+    assert!("80:http".parse::<ListPortsList>().is_err());
+
+    // The flags are not on the command line, so set them in the environment
+    std::env::set_var("LIST_TAGS", "web,prod");
+    std::env::set_var("LIST_PORTS", "80:443");
+
+    let mut config = Config {
+        tags: vec![],
+        ports: None,
+        hosts: vec!["localhost".to_string()],
+    };
+    config.apply_flags();
+
+    assert_eq!(config.tags, vec!["web".to_string(), "prod".to_string()]);
+    assert_eq!(config.ports, Some(vec![80, 443]));
+    assert_eq!(config.hosts, vec!["localhost".to_string()]);
+}
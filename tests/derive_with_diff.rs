@@ -0,0 +1,52 @@
+extern crate gflags_derive;
+use gflags_derive::GFlags;
+
+mod common;
+use common::*;
+
+#[derive(GFlags)]
+#[gflags(prefix = "diff-", generate_diff)]
+#[allow(dead_code)]
+struct Config {
+    /// The directory to write log files to
+    #[gflags(default = "/tmp")]
+    dir: String,
+
+    /// Number of log files to keep
+    #[gflags(default = 10)]
+    keep: u32,
+
+    /// Tag to add to each log line
+    tag: String,
+}
+
+// Runs itself as a child process with the flags to check, see `run_child`
+fn main() {
+    if is_child() {
+        gflags::parse();
+        let config = Config {
+            dir: "".to_string(),
+            keep: 0,
+            tag: "".to_string(),
+        };
+        print!("{}", config.non_default_flags().join(" "));
+        return;
+    }
+
+    // No flags given, nothing differs
+    assert_eq!(child_stdout(&[]), "");
+
+    // Only the flag whose value differs from its default is listed
+    assert_eq!(child_stdout(&["--diff-keep", "5"]), "diff-keep");
+
+    // A flag given with its default value does not differ
+    assert_eq!(
+        child_stdout(&["--diff-dir", "/tmp", "--diff-keep", "5"]),
+        "diff-keep"
+    );
+
+    // A flag without a default differs if it is present
+    assert_eq!(child_stdout(&["--diff-tag", "web"]), "diff-tag");
+
+    println!("derive_with_diff ... ok");
+}
@@ -0,0 +1,45 @@
+extern crate gflags_derive;
+
+mod common;
+use common::*;
+
+// Two structs with the same field names and no prefix, so both register
+// `--dir`. Each is in its own module, so their flag variables do not clash
+mod client {
+    use gflags_derive::GFlags;
+
+    #[derive(GFlags)]
+    #[allow(dead_code)]
+    struct Config {
+        /// The directory to write client log files to
+        dir: String,
+    }
+}
+
+mod server {
+    use gflags_derive::GFlags;
+
+    #[derive(GFlags)]
+    #[allow(dead_code)]
+    struct Config {
+        /// The directory to write server log files to
+        dir: String,
+    }
+}
+
+// Runs itself as a child process, see `run_child`, as the check panics
+fn main() {
+    if is_child() {
+        check_no_duplicate_flags();
+        return;
+    }
+
+    let stderr = child_stderr(&[]);
+    assert!(
+        stderr.contains("Flags registered more than once: [\"dir\"]"),
+        "{}",
+        stderr
+    );
+
+    println!("derive_with_duplicate_flags ... ok");
+}
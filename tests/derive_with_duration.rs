@@ -0,0 +1,61 @@
+extern crate gflags_derive;
+use gflags_derive::GFlags;
+
+mod common;
+use common::*;
+use std::time::Duration;
+
+#[test]
+fn derive_with_duration() {
+    #[derive(GFlags)]
+    #[gflags(prefix = "duration-", generate_apply, auto_env)]
+    struct Config {
+        /// How long to wait for a connection
+        #[gflags(default = "10s")]
+        timeout: Duration,
+
+        /// How often to retry a failed connection
+        retry: Option<Duration>,
+
+        /// How long to keep idle connections open
+        idle: Option<Duration>,
+    }
+
+    let mut flags = fetch_flags();
+
+    // `Duration` should have been converted to `&str`
+    check_flag(
+        Some(ExpectedFlag::<&str> {
+            doc: &["How long to wait for a connection"],
+            name: "duration-timeout",
+            placeholder: None,
+            generated_flag: &DURATION_TIMEOUT,
+        }),
+        flags.remove("duration-timeout"),
+    );
+
+    check_flag(
+        Some(ExpectedFlag::<&str> {
+            doc: &["How often to retry a failed connection"],
+            name: "duration-retry",
+            placeholder: None,
+            generated_flag: &DURATION_RETRY,
+        }),
+        flags.remove("duration-retry"),
+    );
+
+    // The flags are not on the command line, so set them in the environment
+    std::env::set_var("DURATION_TIMEOUT", "30s");
+    std::env::set_var("DURATION_RETRY", "1h30m500ms");
+
+    let mut config = Config {
+        timeout: Duration::from_secs(0),
+        retry: None,
+        idle: None,
+    };
+    config.apply_flags();
+
+    assert_eq!(config.timeout, Duration::from_secs(30));
+    assert_eq!(config.retry, Some(Duration::from_millis(5_400_500)));
+    assert_eq!(config.idle, None);
+}
@@ -0,0 +1,56 @@
+extern crate gflags_derive;
+use gflags_derive::GFlags;
+
+mod common;
+use common::*;
+
+#[test]
+fn derive_with_effective() {
+    #[derive(GFlags)]
+    #[gflags(prefix = "effective-", generate_effective)]
+    #[allow(dead_code)]
+    struct Config {
+        /// The database user
+        #[gflags(default = "admin")]
+        user: String,
+
+        /// The database password
+        #[gflags(secret, default = "hunter2")]
+        password: String,
+
+        /// Seconds to wait for a connection
+        timeout: u32,
+    }
+
+    let mut flags = fetch_flags();
+
+    check_flag(
+        Some(ExpectedFlag::<&str> {
+            doc: &["The database user"],
+            name: "effective-user",
+            placeholder: None,
+            generated_flag: &EFFECTIVE_USER,
+        }),
+        flags.remove("effective-user"),
+    );
+
+    let config = Config {
+        user: EFFECTIVE_USER.flag.to_string(),
+        password: EFFECTIVE_PASSWORD.flag.to_string(),
+        timeout: 0,
+    };
+
+    // The secret value is redacted, and `--effective-timeout` has no value
+    assert_eq!(
+        config.effective_flags(),
+        vec![
+            ("effective-user".to_string(), false, "admin".to_string()),
+            ("effective-password".to_string(), false, "***".to_string()),
+            (
+                "effective-timeout".to_string(),
+                false,
+                "<unset>".to_string()
+            ),
+        ]
+    );
+}
@@ -0,0 +1,58 @@
+extern crate gflags_derive;
+use gflags_derive::GFlags;
+
+mod common;
+use common::*;
+
+#[test]
+fn derive_with_enum() {
+    #[derive(GFlags)]
+    #[gflags(prefix = "mode-")]
+    #[allow(dead_code)]
+    enum Mode {
+        /// Run normally
+        Normal,
+
+        /// Report what would be done, without doing it
+        DryRun,
+
+        /// Run the self tests
+        /// and exit
+        SelfTest,
+    }
+
+    let mut flags = fetch_flags();
+
+    // One `bool` flag per variant, named after the variant
+    check_flag(
+        Some(ExpectedFlag::<bool> {
+            doc: &["Run normally"],
+            name: "mode-normal",
+            placeholder: None,
+            generated_flag: &MODE_NORMAL,
+        }),
+        flags.remove("mode-normal"),
+    );
+
+    check_flag(
+        Some(ExpectedFlag::<bool> {
+            doc: &["Report what would be done, without doing it"],
+            name: "mode-dry-run",
+            placeholder: None,
+            generated_flag: &MODE_DRY_RUN,
+        }),
+        flags.remove("mode-dry-run"),
+    );
+
+    check_flag(
+        Some(ExpectedFlag::<bool> {
+            doc: &["Run the self tests", "and exit"],
+            name: "mode-self-test",
+            placeholder: None,
+            generated_flag: &MODE_SELF_TEST,
+        }),
+        flags.remove("mode-self-test"),
+    );
+
+    assert!(flags.is_empty(), "unexpected flags: {:?}", flags.keys());
+}
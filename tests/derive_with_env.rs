@@ -0,0 +1,76 @@
+extern crate gflags_derive;
+use gflags_derive::GFlags;
+
+mod common;
+use common::*;
+use std::path::PathBuf;
+
+#[test]
+fn derive_with_env() {
+    #[derive(GFlags)]
+    #[gflags(prefix = "env-", generate_apply, auto_env)]
+    struct Config {
+        /// True if log messages should also be sent to STDERR
+        to_stderr: Option<bool>,
+
+        /// The directory to write log files to
+        #[gflags(type = "&str")]
+        dir: PathBuf,
+
+        /// Maximum size of a log file, in KiB
+        max_size: u32,
+    }
+
+    #[derive(GFlags)]
+    #[gflags(prefix = "env-prefix-", generate_apply, env_prefix = "APP")]
+    struct PrefixConfig {
+        /// The user to log in as
+        #[gflags(env = "USER")]
+        user: String,
+
+        /// The password to log in with
+        password: String,
+    }
+
+    let mut flags = fetch_flags();
+
+    check_flag(
+        Some(ExpectedFlag::<u32> {
+            doc: &["Maximum size of a log file, in KiB"],
+            name: "env-max-size",
+            placeholder: None,
+            generated_flag: &ENV_MAX_SIZE,
+        }),
+        flags.remove("env-max-size"),
+    );
+
+    // Environment variable names are derived from the flag names
+    std::env::set_var("ENV_TO_STDERR", "true");
+    std::env::set_var("ENV_DIR", "/var/log");
+    std::env::set_var("ENV_MAX_SIZE", "2048");
+
+    let mut config = Config {
+        to_stderr: None,
+        dir: PathBuf::from("/tmp"),
+        max_size: 1024,
+    };
+    config.apply_flags();
+
+    assert_eq!(config.to_stderr, Some(true));
+    assert_eq!(config.dir, PathBuf::from("/var/log"));
+    assert_eq!(config.max_size, 2048);
+
+    // `env_prefix` is added to `env` names, and fields without `env` are not
+    // read from the environment
+    std::env::set_var("APP_USER", "admin");
+    std::env::set_var("ENV_PREFIX_PASSWORD", "hunter2");
+
+    let mut config = PrefixConfig {
+        user: "nobody".to_string(),
+        password: "".to_string(),
+    };
+    config.apply_flags();
+
+    assert_eq!(config.user, "admin");
+    assert_eq!(config.password, "");
+}
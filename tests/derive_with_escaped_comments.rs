@@ -0,0 +1,38 @@
+extern crate gflags_derive;
+use gflags_derive::GFlags;
+
+mod common;
+use common::*;
+
+#[test]
+fn derive_with_escaped_comments() {
+    #[derive(GFlags)]
+    #[gflags(export_help)]
+    #[allow(dead_code)]
+    struct Config {
+        /// Use "quotes" and \n here
+        /// Paths look like C:\logs\app
+        dir: String,
+    }
+
+    let mut flags = fetch_flags();
+
+    // Quotes and backslashes are preserved exactly, not escaped again
+    check_flag(
+        Some(ExpectedFlag::<&str> {
+            doc: &[
+                "Use \"quotes\" and \\n here",
+                "Paths look like C:\\logs\\app",
+            ],
+            name: "dir",
+            placeholder: None,
+            generated_flag: &DIR,
+        }),
+        flags.remove("dir"),
+    );
+
+    assert_eq!(
+        DIR_HELP,
+        "Use \"quotes\" and \\n here\nPaths look like C:\\logs\\app"
+    );
+}
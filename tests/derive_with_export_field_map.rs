@@ -0,0 +1,47 @@
+extern crate gflags_derive;
+use gflags_derive::GFlags;
+
+mod common;
+use common::*;
+
+#[test]
+fn derive_with_export_field_map() {
+    #[derive(GFlags)]
+    #[gflags(prefix = "map-", export_field_map)]
+    #[allow(dead_code)]
+    struct LogConfig {
+        /// True if log messages should also be sent to STDERR
+        to_stderr: bool,
+
+        /// The directory to write log files to
+        dir: String,
+
+        /// The type of log file
+        r#type: String,
+
+        /// Number of messages logged so far
+        #[gflags(skip)]
+        count: u64,
+    }
+
+    let mut flags = fetch_flags();
+
+    check_flag(
+        Some(ExpectedFlag::<bool> {
+            doc: &["True if log messages should also be sent to STDERR"],
+            name: "map-to-stderr",
+            placeholder: None,
+            generated_flag: &MAP_TO_STDERR,
+        }),
+        flags.remove("map-to-stderr"),
+    );
+
+    assert_eq!(
+        LOG_CONFIG_FLAG_TO_FIELD,
+        &[
+            ("map-to-stderr", "to_stderr"),
+            ("map-dir", "dir"),
+            ("map-type", "type"),
+        ]
+    );
+}
@@ -0,0 +1,63 @@
+extern crate gflags_derive;
+use gflags_derive::GFlags;
+
+mod common;
+use common::*;
+
+// The flag variable is private to the module, but its help is `pub`
+mod server {
+    use gflags_derive::GFlags;
+
+    #[derive(GFlags)]
+    #[gflags(prefix = "server-", export_help)]
+    #[allow(dead_code)]
+    struct Config {
+        /// The port to listen on
+        port: u16,
+    }
+}
+
+#[test]
+fn derive_with_export_help() {
+    #[derive(GFlags)]
+    #[gflags(prefix = "log-", export_help)]
+    #[allow(dead_code)]
+    struct Config {
+        /// True if log messages should also be sent to STDERR
+        to_stderr: bool,
+
+        /// The directory to write log files to
+        ///
+        /// Created if it does not exist
+        dir: String,
+
+        level: u8,
+    }
+
+    let mut flags = fetch_flags();
+
+    check_flag(
+        Some(ExpectedFlag::<bool> {
+            doc: &["True if log messages should also be sent to STDERR"],
+            name: "log-to-stderr",
+            placeholder: None,
+            generated_flag: &LOG_TO_STDERR,
+        }),
+        flags.remove("log-to-stderr"),
+    );
+
+    assert_eq!(
+        LOG_TO_STDERR_HELP,
+        "True if log messages should also be sent to STDERR"
+    );
+
+    assert_eq!(
+        LOG_DIR_HELP,
+        "The directory to write log files to\n\nCreated if it does not exist"
+    );
+
+    // A field without a doc comment has empty help text
+    assert_eq!(LOG_LEVEL_HELP, "");
+
+    assert_eq!(server::SERVER_PORT_HELP, "The port to listen on");
+}
@@ -0,0 +1,58 @@
+// The `cli` feature does not exist, so it is never enabled
+#![allow(unexpected_cfgs)]
+
+extern crate gflags_derive;
+use gflags_derive::GFlags;
+
+mod common;
+use common::*;
+
+#[test]
+fn derive_with_feature() {
+    #[derive(GFlags)]
+    #[gflags(prefix = "cli-", feature = "cli", generate_summary)]
+    #[allow(dead_code)]
+    struct Config {
+        /// True if log messages should also be sent to STDERR
+        to_stderr: bool,
+
+        /// The directory to write log files to
+        #[gflags(default = "/tmp")]
+        dir: String,
+    }
+
+    let mut flags = fetch_flags();
+
+    // The feature is not enabled, so no flags were generated
+    check_flag::<bool>(None, flags.remove("cli-to-stderr"));
+    check_flag::<&str>(None, flags.remove("cli-dir"));
+}
+
+#[test]
+fn derive_with_enabled_feature() {
+    // This crate's own `clap` feature, enabled by `cargo test --all-features`
+    #[derive(GFlags)]
+    #[gflags(prefix = "feature-", feature = "clap")]
+    #[allow(dead_code)]
+    struct Config {
+        /// True if log messages should also be sent to STDERR
+        to_stderr: bool,
+    }
+
+    let mut flags = fetch_flags();
+
+    // The flag is only generated when the feature is enabled
+    #[cfg(feature = "clap")]
+    check_flag(
+        Some(ExpectedFlag::<bool> {
+            doc: &["True if log messages should also be sent to STDERR"],
+            name: "feature-to-stderr",
+            placeholder: None,
+            generated_flag: &FEATURE_TO_STDERR,
+        }),
+        flags.remove("feature-to-stderr"),
+    );
+
+    #[cfg(not(feature = "clap"))]
+    check_flag::<bool>(None, flags.remove("feature-to-stderr"));
+}
@@ -0,0 +1,41 @@
+extern crate gflags_derive;
+use gflags_derive::GFlags;
+
+#[test]
+fn derive_with_field_order() {
+    #[derive(GFlags)]
+    #[gflags(prefix = "order-", export_field_map, group_header = "Ordering")]
+    #[allow(dead_code)]
+    struct OrderConfig {
+        /// Declared first, but sorts last
+        zulu: bool,
+
+        /// Renamed to sort first
+        #[gflags(rename = "alpha")]
+        mike: String,
+
+        /// Not a flag
+        #[gflags(skip)]
+        count: u64,
+
+        /// Declared last, sorts in the middle
+        bravo: u32,
+    }
+
+    // The flags are in field declaration order, not sorted by name
+    assert_eq!(
+        ORDER_CONFIG_FLAG_TO_FIELD,
+        &[
+            ("order-zulu", "zulu"),
+            ("order-alpha", "mike"),
+            ("order-bravo", "bravo"),
+        ]
+    );
+    assert_eq!(
+        ORDER_CONFIG_FLAG_GROUP,
+        (
+            "Ordering",
+            &["order-zulu", "order-alpha", "order-bravo"][..]
+        )
+    );
+}
@@ -0,0 +1,68 @@
+extern crate gflags_derive;
+use gflags_derive::GFlags;
+
+mod common;
+use common::*;
+
+#[derive(GFlags)]
+#[gflags(prefix = "file-", generate_apply)]
+struct Config {
+    /// The token to authenticate with
+    #[gflags(from_file, secret)]
+    token: String,
+
+    /// Number of times to retry
+    #[gflags(from_file)]
+    retries: u32,
+}
+
+// Runs itself as a child process with the flags to check, see `run_child`
+fn main() {
+    if is_child() {
+        gflags::parse();
+        let mut config = Config {
+            token: "".to_string(),
+            retries: 0,
+        };
+        config.apply_flags();
+        println!("{} {}", config.token, config.retries);
+        return;
+    }
+
+    let dir = std::env::temp_dir();
+    let token_path = dir.join(format!(
+        "derive_with_from_file_{}_token",
+        std::process::id()
+    ));
+    let retries_path = dir.join(format!(
+        "derive_with_from_file_{}_retries",
+        std::process::id()
+    ));
+    std::fs::write(&token_path, "s3cret\n").unwrap();
+    std::fs::write(&retries_path, "3\n").unwrap();
+    let token_path = token_path.to_str().unwrap();
+    let retries_path = retries_path.to_str().unwrap();
+
+    // The value is read from the file, without the trailing newline, and
+    // parsed if the field is not a string
+    let stdout = child_stdout(&[
+        "--file-token-file",
+        token_path,
+        "--file-retries-file",
+        retries_path,
+    ]);
+    assert_eq!(stdout, "s3cret 3\n");
+
+    // The flag itself takes precedence over the file
+    let stdout = child_stdout(&["--file-token", "direct", "--file-token-file", token_path]);
+    assert_eq!(stdout, "direct 0\n");
+
+    // A file that can not be read is an error
+    assert!(child_stderr(&["--file-token-file", "/nonexistent/token"])
+        .starts_with("Can not read /nonexistent/token for --file-token-file: "));
+
+    std::fs::remove_file(token_path).unwrap();
+    std::fs::remove_file(retries_path).unwrap();
+
+    println!("derive_with_from_file ... ok");
+}
@@ -0,0 +1,147 @@
+extern crate gflags_derive;
+use gflags_derive::GFlags;
+
+mod common;
+use common::*;
+use std::str::FromStr;
+
+#[test]
+fn derive_with_from_str() {
+    #[derive(Debug, PartialEq)]
+    enum Level {
+        Info,
+        Debug,
+    }
+
+    impl FromStr for Level {
+        type Err = String;
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            match s {
+                "info" => Ok(Level::Info),
+                "debug" => Ok(Level::Debug),
+                _ => Err(format!("invalid level `{}`", s)),
+            }
+        }
+    }
+
+    #[derive(GFlags)]
+    #[allow(dead_code)]
+    struct Config {
+        /// The level to log at
+        #[gflags(from_str)]
+        level: Level,
+    }
+
+    let mut flags = fetch_flags();
+
+    // The flag has a generated type that holds the field's type, parsed with
+    // `Level::from_str`
+    check_flag(
+        Some(ExpectedFlag::<LevelValue> {
+            doc: &["The level to log at"],
+            name: "level",
+            placeholder: None,
+            generated_flag: &LEVEL,
+        }),
+        flags.remove("level"),
+    );
+
+    assert_eq!(Level::from_str("debug"), Ok(Level::Debug));
+    assert_eq!(Level::from_str("info"), Ok(Level::Info));
+    assert_eq!(
+        LevelValue::from_str("debug").map(|value| value.0),
+        Ok(Level::Debug)
+    );
+}
+
+#[test]
+fn derive_with_from_str_foreign_type() {
+    #[derive(GFlags)]
+    #[gflags(prefix = "foreign-")]
+    #[allow(dead_code)]
+    struct Config {
+        /// The address to listen on
+        #[gflags(type = "std::net::Ipv4Addr", from_str)]
+        addr: std::net::Ipv4Addr,
+    }
+
+    let mut flags = fetch_flags();
+
+    // `Ipv4Addr` is from another crate, so `gflags::custom::Value` could not
+    // be implemented for it here
+    check_flag(
+        Some(ExpectedFlag::<ForeignAddrValue> {
+            doc: &["The address to listen on"],
+            name: "foreign-addr",
+            placeholder: None,
+            generated_flag: &FOREIGN_ADDR,
+        }),
+        flags.remove("foreign-addr"),
+    );
+
+    assert_eq!(
+        ForeignAddrValue::from_str("127.0.0.1").map(|value| value.0),
+        Ok(std::net::Ipv4Addr::LOCALHOST)
+    );
+    assert!(ForeignAddrValue::from_str("localhost").is_err());
+}
+
+#[test]
+fn derive_with_from_str_shared_type() {
+    #[derive(Debug, PartialEq)]
+    struct Port(u16);
+
+    impl FromStr for Port {
+        type Err = std::num::ParseIntError;
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            s.parse().map(Port)
+        }
+    }
+
+    #[derive(GFlags)]
+    #[gflags(prefix = "shared-")]
+    #[allow(dead_code)]
+    struct Config {
+        /// The port to listen on
+        #[gflags(from_str)]
+        port: Port,
+
+        /// The port to connect to
+        #[gflags(from_str)]
+        remote_port: Port,
+    }
+
+    let mut flags = fetch_flags();
+
+    // Each flag has its own generated type, so two fields can share a type
+    check_flag(
+        Some(ExpectedFlag::<SharedPortValue> {
+            doc: &["The port to listen on"],
+            name: "shared-port",
+            placeholder: None,
+            generated_flag: &SHARED_PORT,
+        }),
+        flags.remove("shared-port"),
+    );
+
+    check_flag(
+        Some(ExpectedFlag::<SharedRemotePortValue> {
+            doc: &["The port to connect to"],
+            name: "shared-remote-port",
+            placeholder: None,
+            generated_flag: &SHARED_REMOTE_PORT,
+        }),
+        flags.remove("shared-remote-port"),
+    );
+
+    assert_eq!(
+        SharedPortValue::from_str("80").map(|value| value.0),
+        Ok(Port(80))
+    );
+    assert_eq!(
+        SharedRemotePortValue::from_str("443").map(|value| value.0),
+        Ok(Port(443))
+    );
+}
@@ -0,0 +1,44 @@
+extern crate gflags_derive;
+use gflags_derive::GFlags;
+
+mod common;
+use common::*;
+
+#[test]
+fn derive_with_group_header() {
+    #[derive(GFlags)]
+    #[gflags(prefix = "group-", group_header = "Logging")]
+    #[allow(dead_code)]
+    struct LogConfig {
+        /// True if log messages should also be sent to STDERR
+        to_stderr: bool,
+
+        /// The directory to write log files to
+        dir: String,
+
+        #[gflags(skip)]
+        count: u32,
+    }
+
+    let mut flags = fetch_flags();
+
+    let (header, names) = LOG_CONFIG_FLAG_GROUP;
+    assert_eq!(header, "Logging");
+    assert_eq!(names, &["group-to-stderr", "group-dir"]);
+
+    // Each name is a registered flag
+    for name in names {
+        assert!(flags.contains_key(name), "No flag named --{}", name);
+    }
+
+    // The header does not change the flag names
+    check_flag(
+        Some(ExpectedFlag::<bool> {
+            doc: &["True if log messages should also be sent to STDERR"],
+            name: "group-to-stderr",
+            placeholder: None,
+            generated_flag: &GROUP_TO_STDERR,
+        }),
+        flags.remove("group-to-stderr"),
+    );
+}
@@ -0,0 +1,38 @@
+extern crate gflags_derive;
+use gflags_derive::GFlags;
+
+mod common;
+use common::*;
+
+#[test]
+fn derive_with_help_file() {
+    #[derive(GFlags)]
+    #[gflags(prefix = "help-file-")]
+    #[allow(dead_code)]
+    struct Config {
+        /// The directory to write log files to
+        #[gflags(help_file = "tests/help_files/dir.txt")]
+        #[gflags(help_suffix = "Created if it does not exist")]
+        dir: String,
+
+        /// Maximum size of a log file, in bytes
+        max_size: u64,
+    }
+
+    let mut flags = fetch_flags();
+
+    check_flag(
+        Some(ExpectedFlag::<&str> {
+            doc: &[
+                "The directory to write log files to",
+                "Log files are named after the program, and rotated",
+                "when they reach --help-file-max-size bytes.",
+                "Created if it does not exist",
+            ],
+            name: "help-file-dir",
+            placeholder: None,
+            generated_flag: &HELP_FILE_DIR,
+        }),
+        flags.remove("help-file-dir"),
+    );
+}
@@ -0,0 +1,33 @@
+extern crate gflags_derive;
+use gflags_derive::GFlags;
+
+mod common;
+use common::*;
+
+#[test]
+fn derive_with_help_handler() {
+    #[derive(GFlags)]
+    #[gflags(prefix = "help-", generate_help_handler)]
+    #[allow(dead_code)]
+    struct Config {
+        /// True if log messages should also be sent to STDERR
+        to_stderr: bool,
+    }
+
+    // Calling `handle_help()` would parse the test binary's own arguments,
+    // so only check that it has the expected signature. `examples/json`
+    // runs it against a real command line.
+    let _: fn() -> Vec<&'static str> = Config::handle_help;
+
+    let mut flags = fetch_flags();
+
+    check_flag(
+        Some(ExpectedFlag::<bool> {
+            doc: &["True if log messages should also be sent to STDERR"],
+            name: "help-to-stderr",
+            placeholder: None,
+            generated_flag: &HELP_TO_STDERR,
+        }),
+        flags.remove("help-to-stderr"),
+    );
+}
@@ -0,0 +1,56 @@
+extern crate gflags_derive;
+use gflags_derive::GFlags;
+
+mod common;
+use common::*;
+
+#[test]
+fn derive_with_help_indent() {
+    #[derive(GFlags)]
+    #[gflags(prefix = "indent-", help_indent = 4, export_help)]
+    #[allow(dead_code)]
+    struct Config {
+        /// Minimum level of messages to log
+        ///
+        /// Messages below this level are dropped
+        #[gflags(help_suffix = "One of: error, warn, info")]
+        level: String,
+
+        /// The directory to write log files to
+        dir: String,
+    }
+
+    let mut flags = fetch_flags();
+
+    // Lines after the first are indented, empty lines are not
+    check_flag(
+        Some(ExpectedFlag::<&str> {
+            doc: &[
+                "Minimum level of messages to log",
+                "",
+                "    Messages below this level are dropped",
+                "    One of: error, warn, info",
+            ],
+            name: "indent-level",
+            placeholder: None,
+            generated_flag: &INDENT_LEVEL,
+        }),
+        flags.remove("indent-level"),
+    );
+
+    assert_eq!(
+        INDENT_LEVEL_HELP,
+        "Minimum level of messages to log\n\n    Messages below this level are dropped\n    One of: error, warn, info"
+    );
+
+    // A single line has nothing to indent
+    check_flag(
+        Some(ExpectedFlag::<&str> {
+            doc: &["The directory to write log files to"],
+            name: "indent-dir",
+            placeholder: None,
+            generated_flag: &INDENT_DIR,
+        }),
+        flags.remove("indent-dir"),
+    );
+}
@@ -0,0 +1,45 @@
+extern crate gflags_derive;
+use gflags_derive::GFlags;
+
+mod common;
+use common::*;
+
+#[test]
+fn derive_with_help_suffix() {
+    #[derive(GFlags)]
+    #[allow(dead_code)]
+    #[gflags(prefix = "suffix-")]
+    struct Config {
+        /// Minimum level of messages to log
+        #[gflags(help_suffix = "One of: error, warn, info, debug, trace")]
+        level: String,
+
+        #[gflags(help_suffix = "Overrides --suffix-level")]
+        quiet: bool,
+    }
+
+    let mut flags = fetch_flags();
+
+    check_flag(
+        Some(ExpectedFlag::<&str> {
+            doc: &[
+                "Minimum level of messages to log",
+                "One of: error, warn, info, debug, trace",
+            ],
+            name: "suffix-level",
+            placeholder: None,
+            generated_flag: &SUFFIX_LEVEL,
+        }),
+        flags.remove("suffix-level"),
+    );
+
+    check_flag(
+        Some(ExpectedFlag::<bool> {
+            doc: &["Overrides --suffix-level"],
+            name: "suffix-quiet",
+            placeholder: None,
+            generated_flag: &SUFFIX_QUIET,
+        }),
+        flags.remove("suffix-quiet"),
+    );
+}
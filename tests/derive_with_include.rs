@@ -0,0 +1,51 @@
+extern crate gflags_derive;
+
+mod common;
+
+// Brought in with `include!`, like the code `prost-build` writes to `OUT_DIR`,
+// so the test is in the same module as the flags
+mod config {
+    use super::common::*;
+    use gflags_derive::GFlags;
+
+    include!("include/log_config.rs");
+
+    #[test]
+    fn derive_with_include() {
+        let mut flags = fetch_flags();
+
+        check_flag(
+            Some(ExpectedFlag::<bool> {
+                doc: &["True if log messages should also be sent to STDERR"],
+                name: "inc-to-stderr",
+                placeholder: None,
+                generated_flag: &INC_TO_STDERR,
+            }),
+            flags.remove("inc-to-stderr"),
+        );
+
+        check_flag(
+            Some(ExpectedFlag::<&str> {
+                doc: &[
+                    "The directory to write log files to.",
+                    "",
+                    "Created if it does not exist.",
+                ],
+                name: "inc-dir",
+                placeholder: Some("DIR"),
+                generated_flag: &INC_DIR,
+            }),
+            flags.remove("inc-dir"),
+        );
+
+        check_flag(
+            Some(ExpectedFlag::<u32> {
+                doc: &["Maximum size of a log file, in KiB"],
+                name: "inc-max-size",
+                placeholder: None,
+                generated_flag: &INC_MAX_SIZE,
+            }),
+            flags.remove("inc-max-size"),
+        );
+    }
+}
@@ -0,0 +1,53 @@
+extern crate gflags_derive;
+use gflags_derive::GFlags;
+
+mod common;
+use common::*;
+
+#[test]
+fn derive_with_inert_attributes() {
+    // Attributes that are not `#[gflags(...)]`, on the struct and on its
+    // fields, are ignored. `prost` adds several of these to every struct
+    #[derive(Clone, PartialEq, Debug, Default, GFlags)]
+    #[repr(C)]
+    #[must_use]
+    #[allow(dead_code)]
+    #[gflags(prefix = "inert-")]
+    #[cfg_attr(test, derive(Eq))]
+    #[doc = "Configuration for logging"]
+    struct Config {
+        /// True if log messages should also be sent to STDERR
+        #[allow(unused)]
+        to_stderr: bool,
+
+        /// The directory to write log files to
+        #[cfg_attr(not(test), allow(unused))]
+        #[doc = "Created if it does not exist"]
+        dir: String,
+    }
+
+    let mut flags = fetch_flags();
+
+    check_flag(
+        Some(ExpectedFlag::<bool> {
+            doc: &["True if log messages should also be sent to STDERR"],
+            name: "inert-to-stderr",
+            placeholder: None,
+            generated_flag: &INERT_TO_STDERR,
+        }),
+        flags.remove("inert-to-stderr"),
+    );
+
+    check_flag(
+        Some(ExpectedFlag::<&str> {
+            doc: &[
+                "The directory to write log files to",
+                "Created if it does not exist",
+            ],
+            name: "inert-dir",
+            placeholder: None,
+            generated_flag: &INERT_DIR,
+        }),
+        flags.remove("inert-dir"),
+    );
+}
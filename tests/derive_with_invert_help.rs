@@ -0,0 +1,49 @@
+extern crate gflags_derive;
+use gflags_derive::GFlags;
+
+mod common;
+use common::*;
+
+#[test]
+fn derive_with_invert_help() {
+    #[derive(GFlags)]
+    #[gflags(prefix = "invert-")]
+    #[allow(dead_code)]
+    struct Config {
+        /// Compress log files.
+        /// Compression uses gzip
+        #[gflags(default = true, invert_help)]
+        compress: bool,
+
+        #[gflags(default = true, invert_help)]
+        rotate: bool,
+    }
+
+    let mut flags = fetch_flags();
+
+    // The first line says how to turn the flag off
+    check_flag(
+        Some(ExpectedFlag::<bool> {
+            doc: &[
+                "Compress log files. Enabled by default, use --noinvert-compress to disable",
+                "Compression uses gzip",
+            ],
+            name: "invert-compress",
+            placeholder: None,
+            generated_flag: &INVERT_COMPRESS,
+        }),
+        flags.remove("invert-compress"),
+    );
+
+    check_flag(
+        Some(ExpectedFlag::<bool> {
+            doc: &["Enabled by default, use --noinvert-rotate to disable"],
+            name: "invert-rotate",
+            placeholder: None,
+            generated_flag: &INVERT_ROTATE,
+        }),
+        flags.remove("invert-rotate"),
+    );
+
+    assert!(INVERT_COMPRESS.flag);
+}
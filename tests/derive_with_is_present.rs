@@ -0,0 +1,35 @@
+extern crate gflags_derive;
+use gflags_derive::GFlags;
+
+#[test]
+fn derive_with_is_present() {
+    #[derive(GFlags)]
+    #[gflags(prefix = "present-", generate_is_present)]
+    #[allow(dead_code)]
+    struct Config {
+        /// True if log messages should also be sent to STDERR
+        to_stderr: bool,
+
+        /// The directory to write log files to
+        #[gflags(rename = "log-dir")]
+        dir: String,
+
+        /// Not a flag
+        #[gflags(skip)]
+        internal: u32,
+
+        /// Raw identifiers are looked up without the `r#`
+        r#type: String,
+    }
+
+    // Lookups are by field name, not flag name. The flags are not on the
+    // command line, so none are present
+    assert_eq!(Config::flag_is_present("to_stderr"), Some(false));
+    assert_eq!(Config::flag_is_present("dir"), Some(false));
+    assert_eq!(Config::flag_is_present("type"), Some(false));
+
+    // Fields without a flag, and names that are not fields, have no answer
+    assert_eq!(Config::flag_is_present("internal"), None);
+    assert_eq!(Config::flag_is_present("log-dir"), None);
+    assert_eq!(Config::flag_is_present("missing"), None);
+}
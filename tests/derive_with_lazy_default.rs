@@ -0,0 +1,35 @@
+extern crate gflags_derive;
+use gflags_derive::GFlags;
+
+use std::sync::OnceLock;
+
+/// Computed once, the first time it is needed
+fn default_dir() -> &'static str {
+    static DEFAULT_DIR: OnceLock<String> = OnceLock::new();
+    DEFAULT_DIR.get_or_init(|| format!("{}/logs", "/var/tmp"))
+}
+
+#[test]
+fn derive_with_lazy_default() {
+    #[derive(GFlags)]
+    #[gflags(prefix = "lazy-", generate_apply)]
+    struct Config {
+        /// The directory to write log files to
+        dir: String,
+    }
+
+    impl Default for Config {
+        fn default() -> Self {
+            Self {
+                dir: default_dir().to_string(),
+            }
+        }
+    }
+
+    // The flag has no default, so the lazily computed value in the struct is
+    // kept when the flag is not present
+    assert!(!LAZY_DIR.is_present());
+    let mut config = Config::default();
+    config.apply_flags();
+    assert_eq!(config.dir, "/var/tmp/logs");
+}
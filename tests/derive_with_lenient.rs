@@ -0,0 +1,44 @@
+extern crate gflags_derive;
+use gflags_derive::GFlags;
+
+mod common;
+use common::*;
+
+#[test]
+fn derive_with_lenient() {
+    // Keywords this version does not know are ignored, not rejected
+    #[derive(GFlags)]
+    #[gflags(prefix = "lenient-", lenient, from_the_future)]
+    #[allow(dead_code)]
+    struct Config {
+        /// True if log messages should also be sent to STDERR
+        #[gflags(future_key = "value", another_future_key)]
+        to_stderr: bool,
+
+        /// The directory to write log files to
+        #[gflags(placeholder = "DIR", future_key = 42)]
+        dir: String,
+    }
+
+    let mut flags = fetch_flags();
+
+    check_flag(
+        Some(ExpectedFlag::<bool> {
+            doc: &["True if log messages should also be sent to STDERR"],
+            name: "lenient-to-stderr",
+            placeholder: None,
+            generated_flag: &LENIENT_TO_STDERR,
+        }),
+        flags.remove("lenient-to-stderr"),
+    );
+
+    check_flag(
+        Some(ExpectedFlag::<&str> {
+            doc: &["The directory to write log files to"],
+            name: "lenient-dir",
+            placeholder: Some("DIR"),
+            generated_flag: &LENIENT_DIR,
+        }),
+        flags.remove("lenient-dir"),
+    );
+}
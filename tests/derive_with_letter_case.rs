@@ -0,0 +1,46 @@
+extern crate gflags_derive;
+use gflags_derive::GFlags;
+
+mod common;
+use common::*;
+
+#[test]
+fn derive_with_letter_case() {
+    #[derive(GFlags)]
+    #[gflags(prefix = "Log-", lowercase)]
+    #[allow(dead_code, non_snake_case)]
+    struct LowerConfig {
+        /// The directory to write log files to
+        logDir: String,
+    }
+
+    #[derive(GFlags)]
+    #[gflags(uppercase)]
+    #[allow(dead_code, non_snake_case)]
+    struct UpperConfig {
+        /// True if log messages should also be sent to STDERR
+        to_Stderr: bool,
+    }
+
+    let mut flags = fetch_flags();
+
+    check_flag(
+        Some(ExpectedFlag::<&str> {
+            doc: &["The directory to write log files to"],
+            name: "log-logdir",
+            placeholder: None,
+            generated_flag: &LOG_LOGDIR,
+        }),
+        flags.remove("log-logdir"),
+    );
+
+    check_flag(
+        Some(ExpectedFlag::<bool> {
+            doc: &["True if log messages should also be sent to STDERR"],
+            name: "TO-STDERR",
+            placeholder: None,
+            generated_flag: &TO_STDERR,
+        }),
+        flags.remove("TO-STDERR"),
+    );
+}
@@ -0,0 +1,40 @@
+extern crate gflags_derive;
+use gflags_derive::GFlags;
+
+mod common;
+use common::*;
+
+/// Declares a config struct with a single `String` field, and flags for it
+macro_rules! config {
+    ($name:ident, $prefix:literal, $field:ident, $doc:literal) => {
+        #[derive(GFlags)]
+        #[gflags(prefix = $prefix)]
+        #[allow(dead_code)]
+        struct $name {
+            #[doc = $doc]
+            $field: String,
+        }
+    };
+}
+
+config!(
+    LogConfig,
+    "macro-",
+    log_dir,
+    " The directory to write log files to"
+);
+
+#[test]
+fn derive_with_macro_rules() {
+    let mut flags = fetch_flags();
+
+    check_flag(
+        Some(ExpectedFlag::<&str> {
+            doc: &["The directory to write log files to"],
+            name: "macro-log-dir",
+            placeholder: None,
+            generated_flag: &MACRO_LOG_DIR,
+        }),
+        flags.remove("macro-log-dir"),
+    );
+}
@@ -0,0 +1,38 @@
+extern crate gflags_derive;
+use gflags_derive::GFlags;
+
+mod common;
+use common::*;
+
+#[test]
+fn derive_with_markdown_links() {
+    #[derive(GFlags)]
+    #[gflags(prefix = "md-", export_help)]
+    #[allow(dead_code)]
+    struct Config {
+        /// Log format, see [the docs](https://example.com/docs#format)
+        /// Also [`Config`] and [ref][1] (details)
+        format: String,
+    }
+
+    let mut flags = fetch_flags();
+
+    // Brackets and parentheses are passed through to the help unchanged
+    check_flag(
+        Some(ExpectedFlag::<&str> {
+            doc: &[
+                "Log format, see [the docs](https://example.com/docs#format)",
+                "Also [`Config`] and [ref][1] (details)",
+            ],
+            name: "md-format",
+            placeholder: None,
+            generated_flag: &MD_FORMAT,
+        }),
+        flags.remove("md-format"),
+    );
+
+    assert_eq!(
+        MD_FORMAT_HELP,
+        "Log format, see [the docs](https://example.com/docs#format)\nAlso [`Config`] and [ref][1] (details)"
+    );
+}
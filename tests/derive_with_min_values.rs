@@ -0,0 +1,48 @@
+extern crate gflags_derive;
+use gflags_derive::GFlags;
+
+mod common;
+use common::*;
+
+#[derive(GFlags)]
+#[gflags(prefix = "values-", generate_validate)]
+#[allow(dead_code)]
+struct Config {
+    /// Hosts to connect to
+    #[gflags(delimiter = ",", min_values = 2, max_values = 3)]
+    hosts: Vec<String>,
+}
+
+// Runs itself as a child process with the flags to check, see `run_child`
+fn main() {
+    if is_child() {
+        gflags::parse();
+        let config = Config { hosts: vec![] };
+        if let Err(errors) = config.validate() {
+            for error in errors {
+                eprintln!("{}", error);
+            }
+            std::process::exit(1);
+        }
+        return;
+    }
+
+    // Not given, or given with a number of values in the range, is fine
+    child_stdout(&[]);
+    child_stdout(&["--values-hosts", "a,b"]);
+    child_stdout(&["--values-hosts", "a,b,c"]);
+
+    // Too few values
+    assert_eq!(
+        child_stderr(&["--values-hosts", "a"]),
+        "--values-hosts must have at least 2 values, got 1\n"
+    );
+
+    // Too many values
+    assert_eq!(
+        child_stderr(&["--values-hosts", "a,b,c,d"]),
+        "--values-hosts must have at most 3 values, got 4\n"
+    );
+
+    println!("derive_with_min_values ... ok");
+}
@@ -1,5 +1,4 @@
 extern crate gflags_derive;
-use gflags;
 use gflags_derive::GFlags;
 
 mod common;
@@ -0,0 +1,50 @@
+extern crate gflags_derive;
+use gflags_derive::GFlags;
+
+mod common;
+use common::*;
+
+#[test]
+fn derive_with_namespace() {
+    #[derive(GFlags)]
+    #[gflags(namespace = "first", prefix = "log-")]
+    #[allow(dead_code)]
+    struct FirstConfig {
+        /// The directory to write first log files to
+        dir: String,
+    }
+
+    #[derive(GFlags)]
+    #[gflags(namespace = "second", prefix = "log-")]
+    #[allow(dead_code)]
+    struct SecondConfig {
+        /// The directory to write second log files to
+        dir: String,
+    }
+
+    check_no_duplicate_flags();
+
+    let mut flags = fetch_flags();
+
+    check_flag(
+        Some(ExpectedFlag::<&str> {
+            doc: &["The directory to write first log files to"],
+            name: "first-log-dir",
+            placeholder: None,
+            generated_flag: &FIRST_LOG_DIR,
+        }),
+        flags.remove("first-log-dir"),
+    );
+
+    check_flag(
+        Some(ExpectedFlag::<&str> {
+            doc: &["The directory to write second log files to"],
+            name: "second-log-dir",
+            placeholder: None,
+            generated_flag: &SECOND_LOG_DIR,
+        }),
+        flags.remove("second-log-dir"),
+    );
+
+    check_flag::<&str>(None, flags.remove("log-dir"));
+}
@@ -0,0 +1,46 @@
+extern crate gflags_derive;
+use gflags_derive::GFlags;
+
+mod common;
+use common::*;
+
+#[test]
+fn derive_with_only_pub() {
+    #[derive(GFlags)]
+    #[gflags(only_pub)]
+    #[allow(dead_code)]
+    pub struct Config {
+        /// True if log messages should also be sent to STDERR
+        pub to_stderr: bool,
+
+        /// The directory to write log files to
+        pub(crate) dir: String,
+
+        /// Number of messages logged so far
+        count: u64,
+    }
+
+    let mut flags = fetch_flags();
+
+    check_flag(
+        Some(ExpectedFlag::<bool> {
+            doc: &["True if log messages should also be sent to STDERR"],
+            name: "to-stderr",
+            placeholder: None,
+            generated_flag: &TO_STDERR,
+        }),
+        flags.remove("to-stderr"),
+    );
+
+    check_flag(
+        Some(ExpectedFlag::<&str> {
+            doc: &["The directory to write log files to"],
+            name: "dir",
+            placeholder: None,
+            generated_flag: &DIR,
+        }),
+        flags.remove("dir"),
+    );
+
+    check_flag::<u64>(None, flags.remove("count"));
+}
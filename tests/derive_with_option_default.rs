@@ -0,0 +1,43 @@
+extern crate gflags_derive;
+use gflags_derive::GFlags;
+
+mod common;
+use common::*;
+use std::path::PathBuf;
+
+#[test]
+fn derive_with_option_default() {
+    #[derive(GFlags)]
+    #[gflags(prefix = "optdef-", generate_apply)]
+    #[allow(dead_code)]
+    struct Config {
+        /// The directory to write log files to
+        #[gflags(type = "&str")]
+        #[gflags(default = "/tmp")]
+        dir: Option<PathBuf>,
+    }
+
+    let mut flags = fetch_flags();
+
+    // The `Option` is unwrapped, and the flag has the given type and default
+    check_flag(
+        Some(ExpectedFlag::<&str> {
+            doc: &["The directory to write log files to"],
+            name: "optdef-dir",
+            placeholder: None,
+            generated_flag: &OPTDEF_DIR,
+        }),
+        flags.remove("optdef-dir"),
+    );
+
+    assert_eq!(
+        OPTDEF_DIR.flag, "/tmp",
+        "OPTDEF_DIR default value should be `/tmp`"
+    );
+
+    // The `&str` value converts to the field's `PathBuf`, and the field is
+    // only set if the flag is present on the command line
+    let mut config = Config { dir: None };
+    config.apply_flags();
+    assert_eq!(config.dir, None);
+}
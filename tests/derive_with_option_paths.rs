@@ -0,0 +1,54 @@
+extern crate gflags_derive;
+use gflags_derive::GFlags;
+
+mod common;
+use common::*;
+
+#[test]
+fn derive_with_option_paths() {
+    #[derive(GFlags)]
+    #[allow(dead_code)]
+    struct Config {
+        /// The directory to write log files to
+        dir: ::core::option::Option<String>,
+
+        /// The user to write log files as
+        user: ::std::option::Option<String>,
+
+        /// Maximum size of a log file, in KiB
+        max_size: core::option::Option<u32>,
+    }
+
+    let mut flags = fetch_flags();
+
+    // Each `Option<T>` should have been converted to `T`, whatever the path
+    check_flag(
+        Some(ExpectedFlag::<&str> {
+            doc: &["The directory to write log files to"],
+            name: "dir",
+            placeholder: None,
+            generated_flag: &DIR,
+        }),
+        flags.remove("dir"),
+    );
+
+    check_flag(
+        Some(ExpectedFlag::<&str> {
+            doc: &["The user to write log files as"],
+            name: "user",
+            placeholder: None,
+            generated_flag: &USER,
+        }),
+        flags.remove("user"),
+    );
+
+    check_flag(
+        Some(ExpectedFlag::<u32> {
+            doc: &["Maximum size of a log file, in KiB"],
+            name: "max-size",
+            placeholder: None,
+            generated_flag: &MAX_SIZE,
+        }),
+        flags.remove("max-size"),
+    );
+}
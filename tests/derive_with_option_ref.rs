@@ -0,0 +1,52 @@
+extern crate gflags_derive;
+use gflags_derive::GFlags;
+
+mod common;
+use common::*;
+
+#[test]
+fn derive_with_option_ref() {
+    #[derive(GFlags)]
+    #[gflags(prefix = "ref-", generate_apply)]
+    struct Config<'a> {
+        /// The user to log in as
+        user: Option<&'a str>,
+
+        /// The directory to write log files to
+        dir: Option<&'static str>,
+    }
+
+    let mut flags = fetch_flags();
+
+    // `Option<&'a str>` should have been converted to `&str`
+    check_flag(
+        Some(ExpectedFlag::<&str> {
+            doc: &["The user to log in as"],
+            name: "ref-user",
+            placeholder: None,
+            generated_flag: &REF_USER,
+        }),
+        flags.remove("ref-user"),
+    );
+
+    check_flag(
+        Some(ExpectedFlag::<&str> {
+            doc: &["The directory to write log files to"],
+            name: "ref-dir",
+            placeholder: None,
+            generated_flag: &REF_DIR,
+        }),
+        flags.remove("ref-dir"),
+    );
+
+    // No flags are present, so nothing changes
+    let user = String::from("admin");
+    let mut config = Config {
+        user: Some(&user),
+        dir: None,
+    };
+    config.apply_flags();
+
+    assert_eq!(config.user, Some("admin"));
+    assert_eq!(config.dir, None);
+}
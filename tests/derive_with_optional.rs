@@ -0,0 +1,37 @@
+extern crate gflags_derive;
+use gflags_derive::GFlags;
+
+mod common;
+use common::*;
+use std::path::PathBuf;
+
+#[test]
+fn derive_with_optional() {
+    type MaybeDir = Option<PathBuf>;
+
+    #[derive(GFlags)]
+    #[gflags(prefix = "optional-", generate_apply)]
+    struct Config {
+        /// The directory to write log files to
+        #[gflags(type = "&str", optional)]
+        dir: MaybeDir,
+    }
+
+    let mut flags = fetch_flags();
+
+    check_flag(
+        Some(ExpectedFlag::<&str> {
+            doc: &["The directory to write log files to"],
+            name: "optional-dir",
+            placeholder: None,
+            generated_flag: &OPTIONAL_DIR,
+        }),
+        flags.remove("optional-dir"),
+    );
+
+    // `apply_flags` wraps the value in `Some(...)`, which only compiles if
+    // `optional` was recognised. The flag is not present, so `None` remains
+    let mut config = Config { dir: None };
+    config.apply_flags();
+    assert_eq!(config.dir, None);
+}
@@ -0,0 +1,99 @@
+extern crate gflags_derive;
+use gflags_derive::GFlags;
+
+mod common;
+use common::*;
+use std::str::FromStr;
+
+#[derive(Debug)]
+enum Level {
+    Info,
+    Debug,
+}
+
+fn parse_level(s: &str) -> Result<Level, String> {
+    match s {
+        "info" => Ok(Level::Info),
+        "debug" => Ok(Level::Debug),
+        _ => Err("expected info or debug".to_string()),
+    }
+}
+
+#[derive(Debug)]
+struct Color;
+
+impl FromStr for Color {
+    type Err = String;
+
+    fn from_str(_: &str) -> Result<Self, Self::Err> {
+        Err("expected a color".to_string())
+    }
+}
+
+#[derive(GFlags)]
+#[gflags(prefix = "err-")]
+#[allow(dead_code)]
+struct Config {
+    /// The level to log at
+    #[gflags(parse_with = "parse_level")]
+    level: Level,
+
+    /// The level to log to the audit log at
+    #[gflags(parse_with = "parse_level")]
+    audit_level: Level,
+
+    /// The color of log messages
+    #[gflags(from_str)]
+    color: Color,
+
+    /// The color of error messages
+    #[gflags(from_str)]
+    error_color: Color,
+
+    /// Ports to listen on
+    #[gflags(delimiter = ",")]
+    ports: Vec<u16>,
+
+    /// Format of log messages
+    #[gflags(choices = "text, json")]
+    format: String,
+}
+
+// Runs itself as a child process with the flags to check, see `run_child`
+fn main() {
+    if is_child() {
+        gflags::parse();
+        return;
+    }
+
+    // Every generated parser reports the value and the flag, then the reason
+    assert_eq!(
+        child_stderr(&["--err-level", "trace"]),
+        "Failed to parse `--err-level`: invalid value \"trace\" for --err-level: expected info or debug\n"
+    );
+    assert_eq!(
+        child_stderr(&["--err-color", "blue"]),
+        "Failed to parse `--err-color`: invalid value \"blue\" for --err-color: expected a color\n"
+    );
+
+    // Flags of the same type each report their own name
+    assert_eq!(
+        child_stderr(&["--err-audit-level", "trace"]),
+        "Failed to parse `--err-audit-level`: invalid value \"trace\" for --err-audit-level: expected info or debug\n"
+    );
+    assert_eq!(
+        child_stderr(&["--err-error-color", "red"]),
+        "Failed to parse `--err-error-color`: invalid value \"red\" for --err-error-color: expected a color\n"
+    );
+
+    assert_eq!(
+        child_stderr(&["--err-ports", "80,http"]),
+        "Failed to parse `--err-ports`: invalid value \"80,http\" for --err-ports: invalid item \"http\": invalid digit found in string\n"
+    );
+    assert_eq!(
+        child_stderr(&["--err-format", "xml"]),
+        "Failed to parse `--err-format`: invalid value \"xml\" for --err-format: expected one of: text, json\n"
+    );
+
+    println!("derive_with_parse_errors ... ok");
+}
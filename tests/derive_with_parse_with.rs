@@ -0,0 +1,90 @@
+extern crate gflags_derive;
+use gflags_derive::GFlags;
+
+mod common;
+use common::*;
+use std::str::FromStr;
+
+#[test]
+fn derive_with_parse_with() {
+    #[derive(Debug, PartialEq)]
+    enum Level {
+        Info,
+        Debug,
+    }
+
+    fn parse_level(s: &str) -> Result<Level, String> {
+        match s {
+            "info" => Ok(Level::Info),
+            "debug" => Ok(Level::Debug),
+            _ => Err(format!("invalid level `{}`", s)),
+        }
+    }
+
+    #[derive(GFlags)]
+    #[allow(dead_code)]
+    struct Config {
+        /// The level to log at
+        #[gflags(parse_with = "parse_level")]
+        level: Level,
+
+        /// The level to log to STDERR at
+        #[gflags(type = "&str")]
+        stderr_level: Level,
+
+        /// The level to log to the audit log at
+        #[gflags(parse_with = "parse_level")]
+        audit_level: Level,
+    }
+
+    let mut flags = fetch_flags();
+
+    // The flag has a generated type that holds the field's type, parsed by
+    // `parse_level`
+    check_flag(
+        Some(ExpectedFlag::<LevelValue> {
+            doc: &["The level to log at"],
+            name: "level",
+            placeholder: None,
+            generated_flag: &LEVEL,
+        }),
+        flags.remove("level"),
+    );
+
+    // The flag is a string, to be converted later
+    check_flag(
+        Some(ExpectedFlag::<&str> {
+            doc: &["The level to log to STDERR at"],
+            name: "stderr-level",
+            placeholder: None,
+            generated_flag: &STDERR_LEVEL,
+        }),
+        flags.remove("stderr-level"),
+    );
+
+    // Each flag has its own generated type, so two fields can share a type
+    check_flag(
+        Some(ExpectedFlag::<AuditLevelValue> {
+            doc: &["The level to log to the audit log at"],
+            name: "audit-level",
+            placeholder: None,
+            generated_flag: &AUDIT_LEVEL,
+        }),
+        flags.remove("audit-level"),
+    );
+
+    assert_eq!(parse_level("debug"), Ok(Level::Debug));
+    assert_eq!(parse_level("info"), Ok(Level::Info));
+    assert_eq!(
+        LevelValue::from_str("debug").map(|value| value.0),
+        Ok(Level::Debug)
+    );
+    assert_eq!(
+        AuditLevelValue::from_str("info").map(|value| value.0),
+        Ok(Level::Info)
+    );
+    assert_eq!(
+        AuditLevelValue::from_str("trace").map(|value| value.0),
+        Err("invalid level `trace`".to_string())
+    );
+}
@@ -0,0 +1,58 @@
+extern crate gflags_derive;
+use gflags_derive::GFlags;
+
+mod common;
+use common::*;
+
+#[test]
+fn derive_with_placeholder_default() {
+    #[derive(GFlags)]
+    #[gflags(prefix = "log-", placeholder_shows_default)]
+    #[allow(dead_code)]
+    struct Config {
+        /// The directory to write log files to
+        #[gflags(placeholder = "DIR", default = "/tmp")]
+        dir: String,
+
+        /// Maximum size of a log file, in KiB
+        #[gflags(placeholder = "KIB", default = 1024)]
+        max_size: u32,
+
+        /// The user to write log files as
+        #[gflags(placeholder = "USER")]
+        user: String,
+    }
+
+    let mut flags = fetch_flags();
+
+    check_flag(
+        Some(ExpectedFlag::<&str> {
+            doc: &["The directory to write log files to"],
+            name: "log-dir",
+            placeholder: Some("DIR=/tmp"),
+            generated_flag: &LOG_DIR,
+        }),
+        flags.remove("log-dir"),
+    );
+
+    check_flag(
+        Some(ExpectedFlag::<u32> {
+            doc: &["Maximum size of a log file, in KiB"],
+            name: "log-max-size",
+            placeholder: Some("KIB=1024"),
+            generated_flag: &LOG_MAX_SIZE,
+        }),
+        flags.remove("log-max-size"),
+    );
+
+    // No default, so the placeholder is unchanged
+    check_flag(
+        Some(ExpectedFlag::<&str> {
+            doc: &["The user to write log files as"],
+            name: "log-user",
+            placeholder: Some("USER"),
+            generated_flag: &LOG_USER,
+        }),
+        flags.remove("log-user"),
+    );
+}
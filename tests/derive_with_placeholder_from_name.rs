@@ -0,0 +1,70 @@
+extern crate gflags_derive;
+use gflags_derive::GFlags;
+
+mod common;
+use common::*;
+
+#[test]
+fn derive_with_placeholder_from_name() {
+    #[derive(GFlags)]
+    #[gflags(prefix = "log-", placeholder_from_name)]
+    #[allow(dead_code)]
+    struct Config {
+        /// The directory to write log files to
+        dir: String,
+
+        /// Maximum size of a log file, in KiB
+        max_size: u32,
+
+        /// The level to log at
+        #[gflags(placeholder = "LEVEL_NAME")]
+        level: String,
+
+        /// True if log messages should also be sent to STDERR
+        to_stderr: bool,
+    }
+
+    let mut flags = fetch_flags();
+
+    check_flag(
+        Some(ExpectedFlag::<&str> {
+            doc: &["The directory to write log files to"],
+            name: "log-dir",
+            placeholder: Some("DIR"),
+            generated_flag: &LOG_DIR,
+        }),
+        flags.remove("log-dir"),
+    );
+
+    check_flag(
+        Some(ExpectedFlag::<u32> {
+            doc: &["Maximum size of a log file, in KiB"],
+            name: "log-max-size",
+            placeholder: Some("SIZE"),
+            generated_flag: &LOG_MAX_SIZE,
+        }),
+        flags.remove("log-max-size"),
+    );
+
+    // An explicit placeholder takes precedence
+    check_flag(
+        Some(ExpectedFlag::<&str> {
+            doc: &["The level to log at"],
+            name: "log-level",
+            placeholder: Some("LEVEL_NAME"),
+            generated_flag: &LOG_LEVEL,
+        }),
+        flags.remove("log-level"),
+    );
+
+    // `bool` flags have no placeholder
+    check_flag(
+        Some(ExpectedFlag::<bool> {
+            doc: &["True if log messages should also be sent to STDERR"],
+            name: "log-to-stderr",
+            placeholder: None,
+            generated_flag: &LOG_TO_STDERR,
+        }),
+        flags.remove("log-to-stderr"),
+    );
+}
@@ -0,0 +1,51 @@
+extern crate gflags_derive;
+use gflags_derive::GFlags;
+
+mod common;
+use common::*;
+
+#[test]
+fn derive_with_prefix_isolation() {
+    // Two structs with the same field names, kept apart by their prefixes
+    #[derive(GFlags)]
+    #[gflags(prefix = "client-")]
+    #[allow(dead_code)]
+    struct ClientConfig {
+        /// The directory to write client log files to
+        dir: String,
+    }
+
+    #[derive(GFlags)]
+    #[gflags(prefix = "server-")]
+    #[allow(dead_code)]
+    struct ServerConfig {
+        /// The directory to write server log files to
+        dir: String,
+    }
+
+    check_no_duplicate_flags();
+
+    let mut flags = fetch_flags();
+
+    check_flag(
+        Some(ExpectedFlag::<&str> {
+            doc: &["The directory to write client log files to"],
+            name: "client-dir",
+            placeholder: None,
+            generated_flag: &CLIENT_DIR,
+        }),
+        flags.remove("client-dir"),
+    );
+
+    check_flag(
+        Some(ExpectedFlag::<&str> {
+            doc: &["The directory to write server log files to"],
+            name: "server-dir",
+            placeholder: None,
+            generated_flag: &SERVER_DIR,
+        }),
+        flags.remove("server-dir"),
+    );
+
+    check_flag::<&str>(None, flags.remove("dir"));
+}
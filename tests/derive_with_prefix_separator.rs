@@ -0,0 +1,48 @@
+extern crate gflags_derive;
+use gflags_derive::GFlags;
+
+mod common;
+use common::*;
+
+#[test]
+fn derive_with_prefix_separator() {
+    #[derive(GFlags)]
+    #[gflags(prefix = "seg", prefix_separator = "_", word_separator = "-")]
+    #[allow(dead_code)]
+    struct Config {
+        /// True if log messages should also be sent to STDERR
+        to_stderr: bool,
+    }
+
+    // Without `prefix_separator` the prefix is followed by the separator at
+    // the end of the prefix, and only the words of the field name change
+    #[derive(GFlags)]
+    #[gflags(prefix = "part-", word_separator = "_")]
+    #[allow(dead_code)]
+    struct PartConfig {
+        /// The directory to write log files to
+        log_dir: String,
+    }
+
+    let mut flags = fetch_flags();
+
+    check_flag(
+        Some(ExpectedFlag::<bool> {
+            doc: &["True if log messages should also be sent to STDERR"],
+            name: "seg_to-stderr",
+            placeholder: None,
+            generated_flag: &SEG_TO_STDERR,
+        }),
+        flags.remove("seg_to-stderr"),
+    );
+
+    check_flag(
+        Some(ExpectedFlag::<&str> {
+            doc: &["The directory to write log files to"],
+            name: "part-log_dir",
+            placeholder: None,
+            generated_flag: &PART_LOG_DIR,
+        }),
+        flags.remove("part-log_dir"),
+    );
+}
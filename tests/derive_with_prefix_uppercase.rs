@@ -0,0 +1,48 @@
+extern crate gflags_derive;
+use gflags_derive::GFlags;
+
+mod common;
+use common::*;
+
+#[test]
+fn derive_with_prefix_uppercase() {
+    #[derive(GFlags)]
+    #[gflags(prefix = "UPLOG_")]
+    #[allow(dead_code)]
+    struct LogConfig {
+        /// True if log messages should also be sent to STDERR
+        to_stderr: bool,
+    }
+
+    #[derive(GFlags)]
+    #[gflags(prefix = "UPDB-", dedup_prefix)]
+    #[allow(dead_code)]
+    struct DbConfig {
+        /// The database user
+        updb_user: String,
+    }
+
+    let mut flags = fetch_flags();
+
+    // The prefix is lowercased to match the rest of the flag name
+    check_flag(
+        Some(ExpectedFlag::<bool> {
+            doc: &["True if log messages should also be sent to STDERR"],
+            name: "uplog_to_stderr",
+            placeholder: None,
+            generated_flag: &UPLOG_TO_STDERR,
+        }),
+        flags.remove("uplog_to_stderr"),
+    );
+
+    // The lowercased prefix is also removed from the start of field names
+    check_flag(
+        Some(ExpectedFlag::<&str> {
+            doc: &["The database user"],
+            name: "updb-user",
+            placeholder: None,
+            generated_flag: &UPDB_USER,
+        }),
+        flags.remove("updb-user"),
+    );
+}
@@ -0,0 +1,61 @@
+extern crate gflags_derive;
+
+mod common;
+use common::*;
+
+// Fields restricted with `pub(in path)` count as `pub` for
+// `#[gflags(only_pub)]`. The visibility of the field does not change the
+// visibility of the flag, so `visibility` is still needed to reach the flag
+// variables from outside the module.
+mod outer {
+    pub mod inner {
+        use gflags_derive::GFlags;
+
+        #[derive(GFlags)]
+        #[gflags(prefix = "pub-in-", only_pub)]
+        #[allow(dead_code)]
+        pub struct Config {
+            /// True if log messages should also be sent to STDERR
+            #[gflags(visibility = "pub(in crate::outer)")]
+            pub(in crate::outer) to_stderr: bool,
+
+            /// The directory to write log files to
+            #[gflags(visibility = "pub(crate)")]
+            pub(in crate::outer::inner) dir: String,
+
+            /// Number of messages logged so far
+            count: u64,
+        }
+    }
+
+    pub fn to_stderr() -> &'static gflags::Flag<bool> {
+        &inner::PUB_IN_TO_STDERR
+    }
+}
+
+#[test]
+fn derive_with_pub_in_path() {
+    let mut flags = fetch_flags();
+
+    check_flag(
+        Some(ExpectedFlag::<bool> {
+            doc: &["True if log messages should also be sent to STDERR"],
+            name: "pub-in-to-stderr",
+            placeholder: None,
+            generated_flag: outer::to_stderr(),
+        }),
+        flags.remove("pub-in-to-stderr"),
+    );
+
+    check_flag(
+        Some(ExpectedFlag::<&str> {
+            doc: &["The directory to write log files to"],
+            name: "pub-in-dir",
+            placeholder: None,
+            generated_flag: &outer::inner::PUB_IN_DIR,
+        }),
+        flags.remove("pub-in-dir"),
+    );
+
+    check_flag::<u64>(None, flags.remove("pub-in-count"));
+}
@@ -0,0 +1,57 @@
+extern crate gflags_derive;
+use gflags_derive::GFlags;
+
+mod common;
+use common::*;
+
+#[test]
+fn derive_with_range_help() {
+    #[derive(GFlags)]
+    #[gflags(prefix = "range-", generate_validate)]
+    #[allow(dead_code)]
+    struct Config {
+        /// Number of worker threads
+        #[gflags(min = 1)]
+        threads: u32,
+
+        /// Number of retries
+        #[gflags(max = 10)]
+        retries: u32,
+
+        /// Fraction of messages to sample
+        #[gflags(min = 0.0, max = 1.0)]
+        sample_rate: f64,
+    }
+
+    let mut flags = fetch_flags();
+
+    check_flag(
+        Some(ExpectedFlag::<u32> {
+            doc: &["Number of worker threads", "[range: 1..]"],
+            name: "range-threads",
+            placeholder: None,
+            generated_flag: &RANGE_THREADS,
+        }),
+        flags.remove("range-threads"),
+    );
+
+    check_flag(
+        Some(ExpectedFlag::<u32> {
+            doc: &["Number of retries", "[range: ..=10]"],
+            name: "range-retries",
+            placeholder: None,
+            generated_flag: &RANGE_RETRIES,
+        }),
+        flags.remove("range-retries"),
+    );
+
+    check_flag(
+        Some(ExpectedFlag::<f64> {
+            doc: &["Fraction of messages to sample", "[range: 0.0..=1.0]"],
+            name: "range-sample-rate",
+            placeholder: None,
+            generated_flag: &RANGE_SAMPLE_RATE,
+        }),
+        flags.remove("range-sample-rate"),
+    );
+}
@@ -0,0 +1,49 @@
+extern crate gflags_derive;
+use gflags_derive::GFlags;
+
+mod common;
+use common::*;
+
+#[test]
+fn derive_with_raw_identifiers() {
+    #[derive(GFlags)]
+    #[gflags(prefix = "raw-", generate_apply)]
+    struct Config {
+        /// The type of log file to write
+        r#type: String,
+
+        /// Only log messages that match this pattern
+        r#match: Option<String>,
+    }
+
+    let mut flags = fetch_flags();
+
+    // The `r#` is not part of the flag name, or the static's name
+    check_flag(
+        Some(ExpectedFlag::<&str> {
+            doc: &["The type of log file to write"],
+            name: "raw-type",
+            placeholder: None,
+            generated_flag: &RAW_TYPE,
+        }),
+        flags.remove("raw-type"),
+    );
+
+    check_flag(
+        Some(ExpectedFlag::<&str> {
+            doc: &["Only log messages that match this pattern"],
+            name: "raw-match",
+            placeholder: None,
+            generated_flag: &RAW_MATCH,
+        }),
+        flags.remove("raw-match"),
+    );
+
+    let mut config = Config {
+        r#type: "text".to_string(),
+        r#match: None,
+    };
+    config.apply_flags();
+    assert_eq!(config.r#type, "text");
+    assert_eq!(config.r#match, None);
+}
@@ -0,0 +1,51 @@
+extern crate gflags_derive;
+use gflags_derive::GFlags;
+
+mod common;
+use common::*;
+use std::path::PathBuf;
+
+#[test]
+fn derive_with_raw_string_default() {
+    #[derive(GFlags)]
+    #[allow(dead_code)]
+    struct Config {
+        /// The directory to write log files to
+        #[gflags(type = "&str")]
+        #[gflags(default = r"C:\logs\app")]
+        dir: PathBuf,
+
+        /// Format string for log lines
+        #[gflags(default = r#"{"msg": "{}"}"#)]
+        format: String,
+    }
+
+    let mut flags = fetch_flags();
+
+    check_flag(
+        Some(ExpectedFlag::<&str> {
+            doc: &["The directory to write log files to"],
+            name: "dir",
+            placeholder: None,
+            generated_flag: &DIR,
+        }),
+        flags.remove("dir"),
+    );
+
+    assert_eq!(
+        DIR.flag, "C:\\logs\\app",
+        "DIR default value should be `C:\\logs\\app`"
+    );
+
+    check_flag(
+        Some(ExpectedFlag::<&str> {
+            doc: &["Format string for log lines"],
+            name: "format",
+            placeholder: None,
+            generated_flag: &FORMAT,
+        }),
+        flags.remove("format"),
+    );
+
+    assert_eq!(FORMAT.flag, "{\"msg\": \"{}\"}");
+}
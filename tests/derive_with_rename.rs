@@ -0,0 +1,61 @@
+extern crate gflags_derive;
+use gflags_derive::GFlags;
+
+mod common;
+use common::*;
+
+#[test]
+fn derive_with_rename() {
+    #[derive(GFlags)]
+    #[gflags(prefix = "rename-")]
+    #[allow(dead_code)]
+    struct Config {
+        /// True if log messages should also be sent to STDERR
+        #[gflags(rename = "stderr")]
+        to_stderr: bool,
+
+        /// The directory to write log files to
+        #[gflags(name = "log-dir")]
+        dir: String,
+
+        /// Maximum size of a log file, in KiB
+        max_size: u32,
+    }
+
+    let mut flags = fetch_flags();
+
+    // The renamed flags keep the prefix
+    check_flag(
+        Some(ExpectedFlag::<bool> {
+            doc: &["True if log messages should also be sent to STDERR"],
+            name: "rename-stderr",
+            placeholder: None,
+            generated_flag: &RENAME_STDERR,
+        }),
+        flags.remove("rename-stderr"),
+    );
+
+    check_flag::<bool>(None, flags.remove("rename-to-stderr"));
+
+    check_flag(
+        Some(ExpectedFlag::<&str> {
+            doc: &["The directory to write log files to"],
+            name: "rename-log-dir",
+            placeholder: None,
+            generated_flag: &RENAME_LOG_DIR,
+        }),
+        flags.remove("rename-log-dir"),
+    );
+
+    check_flag::<&str>(None, flags.remove("rename-dir"));
+
+    check_flag(
+        Some(ExpectedFlag::<u32> {
+            doc: &["Maximum size of a log file, in KiB"],
+            name: "rename-max-size",
+            placeholder: None,
+            generated_flag: &RENAME_MAX_SIZE,
+        }),
+        flags.remove("rename-max-size"),
+    );
+}
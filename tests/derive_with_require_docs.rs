@@ -0,0 +1,34 @@
+extern crate gflags_derive;
+use gflags_derive::GFlags;
+
+mod common;
+use common::*;
+
+#[test]
+fn derive_with_require_docs() {
+    #[derive(GFlags)]
+    #[gflags(require_docs)]
+    #[allow(dead_code)]
+    struct Config {
+        /// True if log messages should also be sent to STDERR
+        to_stderr: bool,
+
+        // Skipped fields do not need a doc comment
+        #[gflags(skip)]
+        dir: String,
+    }
+
+    let mut flags = fetch_flags();
+
+    check_flag(
+        Some(ExpectedFlag::<bool> {
+            doc: &["True if log messages should also be sent to STDERR"],
+            name: "to-stderr",
+            placeholder: None,
+            generated_flag: &TO_STDERR,
+        }),
+        flags.remove("to-stderr"),
+    );
+
+    check_flag::<bool>(None, flags.remove("dir"));
+}
@@ -0,0 +1,53 @@
+extern crate gflags_derive;
+use gflags_derive::GFlags;
+
+mod common;
+use common::*;
+use serde::Deserialize;
+
+#[test]
+fn derive_with_serde_flatten() {
+    #[derive(Deserialize, GFlags)]
+    #[gflags(prefix = "log-")]
+    #[allow(dead_code)]
+    struct LogConfig {
+        /// The directory to write log files to
+        dir: String,
+    }
+
+    #[derive(Deserialize, GFlags)]
+    #[gflags(prefix = "app-", respect_serde_flatten)]
+    #[allow(dead_code)]
+    struct Config {
+        /// The name of the application
+        name: String,
+
+        #[serde(flatten)]
+        log: LogConfig,
+    }
+
+    let mut flags = fetch_flags();
+
+    check_flag(
+        Some(ExpectedFlag::<&str> {
+            doc: &["The name of the application"],
+            name: "app-name",
+            placeholder: None,
+            generated_flag: &APP_NAME,
+        }),
+        flags.remove("app-name"),
+    );
+
+    check_flag(
+        Some(ExpectedFlag::<&str> {
+            doc: &["The directory to write log files to"],
+            name: "log-dir",
+            placeholder: None,
+            generated_flag: &LOG_DIR,
+        }),
+        flags.remove("log-dir"),
+    );
+
+    // The flattened field has no flag of its own
+    check_flag::<&str>(None, flags.remove("app-log"));
+}
@@ -0,0 +1,55 @@
+extern crate gflags_derive;
+use gflags_derive::GFlags;
+
+mod common;
+use common::*;
+
+const DEFAULT_DIR: &str = "/tmp";
+
+#[test]
+fn derive_with_shared_default() {
+    #[derive(GFlags)]
+    #[gflags(prefix = "shared-")]
+    #[allow(dead_code)]
+    struct Config {
+        /// The directory to write log files to
+        #[gflags(default = DEFAULT_DIR)]
+        dir: String,
+
+        /// The directory to write archived log files to
+        #[gflags(default_expr = "DEFAULT_DIR")]
+        archive_dir: String,
+    }
+
+    let mut flags = fetch_flags();
+
+    check_flag(
+        Some(ExpectedFlag::<&str> {
+            doc: &["The directory to write log files to"],
+            name: "shared-dir",
+            placeholder: None,
+            generated_flag: &SHARED_DIR,
+        }),
+        flags.remove("shared-dir"),
+    );
+
+    check_flag(
+        Some(ExpectedFlag::<&str> {
+            doc: &["The directory to write archived log files to"],
+            name: "shared-archive-dir",
+            placeholder: None,
+            generated_flag: &SHARED_ARCHIVE_DIR,
+        }),
+        flags.remove("shared-archive-dir"),
+    );
+
+    assert_eq!(SHARED_ARCHIVE_DIR.flag, SHARED_DIR.flag);
+
+    // Defaulting to the other flag's value happens when the program runs
+    let archive_dir = if SHARED_ARCHIVE_DIR.is_present() {
+        SHARED_ARCHIVE_DIR.flag
+    } else {
+        SHARED_DIR.flag
+    };
+    assert_eq!(archive_dir, "/tmp");
+}
@@ -0,0 +1,53 @@
+extern crate gflags_derive;
+use gflags_derive::GFlags;
+
+mod common;
+use common::*;
+
+#[derive(GFlags)]
+#[gflags(prefix = "single-", generate_validate)]
+#[allow(dead_code)]
+struct Config {
+    /// The directory to write log files to
+    #[gflags(single)]
+    dir: String,
+
+    /// Tag to add to each log line
+    tag: String,
+}
+
+// Runs itself as a child process with the flags to check, see `run_child`
+fn main() {
+    if is_child() {
+        gflags::parse();
+        let config = Config {
+            dir: "".to_string(),
+            tag: "".to_string(),
+        };
+        if let Err(errors) = config.validate() {
+            for error in errors {
+                eprintln!("{}", error);
+            }
+            std::process::exit(1);
+        }
+        return;
+    }
+
+    // Given once, and a repeated flag without `single`, is fine
+    child_stdout(&[
+        "--single-dir",
+        "/tmp",
+        "--single-tag",
+        "a",
+        "--single-tag",
+        "b",
+    ]);
+
+    // Given twice is an error
+    assert_eq!(
+        child_stderr(&["--single-dir", "/tmp", "--single-dir", "/var/log"]),
+        "--single-dir must not be given more than once\n"
+    );
+
+    println!("derive_with_single ... ok");
+}
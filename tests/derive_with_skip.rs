@@ -1,5 +1,4 @@
 extern crate gflags_derive;
-use gflags;
 use gflags_derive::GFlags;
 
 mod common;
@@ -16,6 +15,14 @@ fn derive_with_skip() {
         /// The directory to write log files to
         #[gflags(skip)]
         dir: String,
+
+        /// Other options in the same attribute as `skip` are still parsed
+        #[gflags(skip, type = "&str")]
+        path: std::path::PathBuf,
+
+        /// `skip` takes precedence over a `default` in the same attribute
+        #[gflags(skip, default = 1)]
+        count: u32,
     }
 
     let mut flags = fetch_flags();
@@ -31,4 +38,6 @@ fn derive_with_skip() {
     );
 
     check_flag::<bool>(None, flags.remove("dir"));
+    check_flag::<&str>(None, flags.remove("path"));
+    check_flag::<u32>(None, flags.remove("count"));
 }
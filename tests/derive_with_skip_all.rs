@@ -0,0 +1,39 @@
+extern crate gflags_derive;
+use gflags_derive::GFlags;
+
+mod common;
+use common::*;
+
+#[test]
+fn derive_with_skip_all() {
+    #[derive(GFlags)]
+    #[gflags(skip_all)]
+    #[allow(dead_code)]
+    struct Config {
+        /// True if log messages should also be sent to STDERR
+        #[gflags(skip = false)]
+        to_stderr: bool,
+
+        /// The directory to write log files to
+        dir: String,
+
+        /// Number of messages logged so far
+        #[gflags(skip = true)]
+        count: u64,
+    }
+
+    let mut flags = fetch_flags();
+
+    check_flag(
+        Some(ExpectedFlag::<bool> {
+            doc: &["True if log messages should also be sent to STDERR"],
+            name: "to-stderr",
+            placeholder: None,
+            generated_flag: &TO_STDERR,
+        }),
+        flags.remove("to-stderr"),
+    );
+
+    check_flag::<&str>(None, flags.remove("dir"));
+    check_flag::<u64>(None, flags.remove("count"));
+}
@@ -0,0 +1,46 @@
+extern crate gflags_derive;
+use gflags_derive::GFlags;
+
+mod common;
+use common::*;
+
+#[test]
+fn derive_with_suffixed_default() {
+    #[derive(GFlags)]
+    #[allow(dead_code)]
+    struct Config {
+        /// Number of messages to buffer
+        #[gflags(default = 100u64)]
+        count: u64,
+
+        /// Fraction of messages to sample
+        #[gflags(default = 0.5f32)]
+        sample_rate: Option<f32>,
+    }
+
+    let mut flags = fetch_flags();
+
+    check_flag(
+        Some(ExpectedFlag::<u64> {
+            doc: &["Number of messages to buffer"],
+            name: "count",
+            placeholder: None,
+            generated_flag: &COUNT,
+        }),
+        flags.remove("count"),
+    );
+
+    assert_eq!(COUNT.flag, 100);
+
+    check_flag(
+        Some(ExpectedFlag::<f32> {
+            doc: &["Fraction of messages to sample"],
+            name: "sample-rate",
+            placeholder: None,
+            generated_flag: &SAMPLE_RATE,
+        }),
+        flags.remove("sample-rate"),
+    );
+
+    assert_eq!(SAMPLE_RATE.flag, 0.5);
+}
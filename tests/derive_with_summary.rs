@@ -0,0 +1,42 @@
+extern crate gflags_derive;
+use gflags_derive::GFlags;
+
+mod common;
+use common::*;
+
+#[test]
+fn derive_with_summary() {
+    #[derive(GFlags)]
+    #[gflags(prefix = "db-", generate_summary)]
+    #[allow(dead_code)]
+    struct Config {
+        /// The database user
+        #[gflags(default = "admin")]
+        user: String,
+
+        /// The database password
+        #[gflags(secret, default = "hunter2")]
+        password: String,
+
+        /// Seconds to wait for a connection
+        timeout: u32,
+    }
+
+    let mut flags = fetch_flags();
+
+    check_flag(
+        Some(ExpectedFlag::<&str> {
+            doc: &["The database password"],
+            name: "db-password",
+            placeholder: None,
+            generated_flag: &DB_PASSWORD,
+        }),
+        flags.remove("db-password"),
+    );
+
+    // The secret value is redacted, and `--db-timeout` has no value
+    assert_eq!(
+        Config::flags_summary(),
+        "--db-user: \"admin\"\n--db-password: ***\n--db-timeout: <unset>\n"
+    );
+}
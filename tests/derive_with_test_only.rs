@@ -0,0 +1,35 @@
+extern crate gflags_derive;
+use gflags_derive::GFlags;
+
+mod common;
+use common::*;
+
+// Integration tests are compiled with `cfg(test)`, so the flags exist here.
+// `expected_failures/test_only_outside_test.rs` checks that they do not
+// exist otherwise.
+#[test]
+fn derive_with_test_only() {
+    #[derive(GFlags)]
+    #[gflags(prefix = "test-only-", test_only, generate_apply)]
+    #[allow(dead_code)]
+    struct Config {
+        /// Seed for the random number generator
+        seed: u64,
+    }
+
+    let mut flags = fetch_flags();
+
+    check_flag(
+        Some(ExpectedFlag::<u64> {
+            doc: &["Seed for the random number generator"],
+            name: "test-only-seed",
+            placeholder: None,
+            generated_flag: &TEST_ONLY_SEED,
+        }),
+        flags.remove("test-only-seed"),
+    );
+
+    let mut config = Config { seed: 42 };
+    config.apply_flags();
+    assert_eq!(config.seed, 42);
+}
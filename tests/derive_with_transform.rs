@@ -0,0 +1,56 @@
+extern crate gflags_derive;
+use gflags_derive::GFlags;
+
+mod common;
+use common::*;
+
+#[test]
+fn derive_with_transform() {
+    #[derive(GFlags)]
+    #[gflags(prefix = "transform-", transform = "strip_config_suffix")]
+    #[gflags(transform = "trim_underscores")]
+    #[allow(dead_code)]
+    struct Config {
+        /// The directory to write log files to
+        dir_config: String,
+
+        /// Maximum size of a log file, in KiB
+        _max_size_config: u32,
+
+        /// Only `_config`, which is not removed
+        config: bool,
+    }
+
+    let mut flags = fetch_flags();
+
+    check_flag(
+        Some(ExpectedFlag::<&str> {
+            doc: &["The directory to write log files to"],
+            name: "transform-dir",
+            placeholder: None,
+            generated_flag: &TRANSFORM_DIR,
+        }),
+        flags.remove("transform-dir"),
+    );
+
+    // Both transforms apply, in order
+    check_flag(
+        Some(ExpectedFlag::<u32> {
+            doc: &["Maximum size of a log file, in KiB"],
+            name: "transform-max-size",
+            placeholder: None,
+            generated_flag: &TRANSFORM_MAX_SIZE,
+        }),
+        flags.remove("transform-max-size"),
+    );
+
+    check_flag(
+        Some(ExpectedFlag::<bool> {
+            doc: &["Only `_config`, which is not removed"],
+            name: "transform-config",
+            placeholder: None,
+            generated_flag: &TRANSFORM_CONFIG,
+        }),
+        flags.remove("transform-config"),
+    );
+}
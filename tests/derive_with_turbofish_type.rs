@@ -0,0 +1,56 @@
+extern crate gflags_derive;
+use gflags_derive::GFlags;
+
+mod common;
+use common::*;
+
+#[test]
+fn derive_with_turbofish_type() {
+    #[derive(Debug, PartialEq)]
+    struct Port<T>(T);
+
+    impl gflags::custom::Value for Port<u16> {
+        fn parse(arg: gflags::custom::Arg) -> gflags::custom::Result<Self> {
+            arg.get_str()
+                .parse()
+                .map(Port)
+                .map_err(gflags::custom::Error::new)
+        }
+    }
+
+    #[derive(GFlags)]
+    #[gflags(prefix = "turbofish-")]
+    #[allow(dead_code)]
+    struct Config {
+        /// Port to listen on
+        #[gflags(type = "Port::<u16>")]
+        port: u16,
+
+        /// Port to connect to
+        #[gflags(type = "Port<u16>")]
+        remote_port: u16,
+    }
+
+    let mut flags = fetch_flags();
+
+    // The turbofish form is the same type as the plain form
+    check_flag(
+        Some(ExpectedFlag::<Port<u16>> {
+            doc: &["Port to listen on"],
+            name: "turbofish-port",
+            placeholder: None,
+            generated_flag: &TURBOFISH_PORT,
+        }),
+        flags.remove("turbofish-port"),
+    );
+
+    check_flag(
+        Some(ExpectedFlag::<Port<u16>> {
+            doc: &["Port to connect to"],
+            name: "turbofish-remote-port",
+            placeholder: None,
+            generated_flag: &TURBOFISH_REMOTE_PORT,
+        }),
+        flags.remove("turbofish-remote-port"),
+    );
+}
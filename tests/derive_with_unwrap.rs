@@ -0,0 +1,49 @@
+extern crate gflags_derive;
+use gflags_derive::GFlags;
+
+mod common;
+use common::*;
+
+#[allow(dead_code)]
+enum Maybe<T> {
+    Just(T),
+    Nothing,
+}
+
+#[test]
+fn derive_with_unwrap() {
+    #[derive(GFlags)]
+    #[gflags(unwrap = "Maybe")]
+    #[allow(dead_code)]
+    struct Config {
+        /// True if log messages should also be sent to STDERR
+        to_stderr: Option<bool>,
+
+        /// The directory to write log files to
+        dir: Maybe<String>,
+    }
+
+    let mut flags = fetch_flags();
+
+    // `Option<T>` is still unwrapped
+    check_flag(
+        Some(ExpectedFlag::<bool> {
+            doc: &["True if log messages should also be sent to STDERR"],
+            name: "to-stderr",
+            placeholder: None,
+            generated_flag: &TO_STDERR,
+        }),
+        flags.remove("to-stderr"),
+    );
+
+    // `Maybe<String>` should have been converted to `&str`
+    check_flag(
+        Some(ExpectedFlag::<&str> {
+            doc: &["The directory to write log files to"],
+            name: "dir",
+            placeholder: None,
+            generated_flag: &DIR,
+        }),
+        flags.remove("dir"),
+    );
+}
@@ -0,0 +1,57 @@
+extern crate gflags_derive;
+use gflags_derive::GFlags;
+
+mod common;
+use common::*;
+
+#[test]
+fn derive_with_validate() {
+    #[derive(GFlags)]
+    #[gflags(prefix = "validate-", generate_validate)]
+    struct Config {
+        /// The directory to write log files to
+        #[gflags(required)]
+        dir: String,
+
+        /// The user to write log files as
+        #[gflags(required)]
+        user: String,
+
+        /// Maximum size of a log file, in KiB
+        #[gflags(min = 1, max = 1024, requires = "dir")]
+        max_size: u32,
+
+        /// Fraction of messages to sample
+        #[gflags(min = 0.0, max = 1.0)]
+        sample_rate: f64,
+    }
+
+    let mut flags = fetch_flags();
+
+    check_flag(
+        Some(ExpectedFlag::<u32> {
+            doc: &["Maximum size of a log file, in KiB", "[range: 1..=1024]"],
+            name: "validate-max-size",
+            placeholder: None,
+            generated_flag: &VALIDATE_MAX_SIZE,
+        }),
+        flags.remove("validate-max-size"),
+    );
+
+    let config = Config {
+        dir: "".to_string(),
+        user: "".to_string(),
+        max_size: 0,
+        sample_rate: 0.0,
+    };
+
+    // No flags are present, so both required flags are reported, and the
+    // other checks only apply to flags that are present
+    assert_eq!(
+        config.validate(),
+        Err(vec![
+            "--validate-dir is required".to_string(),
+            "--validate-user is required".to_string(),
+        ])
+    );
+}
@@ -0,0 +1,76 @@
+extern crate gflags_derive;
+use gflags_derive::GFlags;
+
+mod common;
+use common::*;
+use std::collections::BTreeMap;
+
+#[test]
+fn derive_with_value_map() {
+    // Implements `Debug` but not `Display`
+    #[derive(Debug)]
+    enum Level {
+        Info,
+    }
+
+    fn parse_level(s: &str) -> Result<Level, String> {
+        match s {
+            "info" => Ok(Level::Info),
+            _ => Err(format!("invalid level `{}`", s)),
+        }
+    }
+
+    #[derive(GFlags)]
+    #[gflags(prefix = "map-", generate_value_map)]
+    #[allow(dead_code)]
+    struct Config {
+        /// The database user
+        #[gflags(default = "admin")]
+        user: String,
+
+        /// The database password
+        #[gflags(secret, default = "hunter2")]
+        password: String,
+
+        /// Seconds to wait for a connection
+        #[gflags(default = 30)]
+        timeout: u32,
+
+        /// The level to log at
+        #[gflags(parse_with = "parse_level", default = Level::Info)]
+        level: Level,
+
+        /// Number of connections to open
+        connections: u32,
+    }
+
+    let mut flags = fetch_flags();
+
+    check_flag(
+        Some(ExpectedFlag::<&str> {
+            doc: &["The database user"],
+            name: "map-user",
+            placeholder: None,
+            generated_flag: &MAP_USER,
+        }),
+        flags.remove("map-user"),
+    );
+
+    let config = Config {
+        user: MAP_USER.flag.to_string(),
+        password: MAP_PASSWORD.flag.to_string(),
+        timeout: MAP_TIMEOUT.flag,
+        level: Level::Info,
+        connections: 1,
+    };
+
+    // `Display` is used if the type implements it, otherwise `Debug`
+    let mut want = BTreeMap::new();
+    want.insert("map-connections".to_string(), "<unset>".to_string());
+    want.insert("map-level".to_string(), "Info".to_string());
+    want.insert("map-password".to_string(), "***".to_string());
+    want.insert("map-timeout".to_string(), "30".to_string());
+    want.insert("map-user".to_string(), "admin".to_string());
+
+    assert_eq!(config.flag_value_strings(), want);
+}
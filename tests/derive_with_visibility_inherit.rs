@@ -0,0 +1,48 @@
+extern crate gflags_derive;
+
+mod common;
+use common::*;
+
+// `#[gflags(visibility = "inherit")]` gives the flag the visibility of its
+// field, so the flags for the `pub(crate)` fields are visible here
+mod config {
+    use gflags_derive::GFlags;
+
+    #[derive(GFlags)]
+    #[gflags(prefix = "inherit-")]
+    #[allow(dead_code)]
+    pub struct Config {
+        /// True if log messages should also be sent to STDERR
+        #[gflags(visibility = "inherit")]
+        pub(crate) to_stderr: bool,
+
+        /// The directory to write log files to
+        #[gflags(visibility = "inherit")]
+        pub(crate) dir: String,
+    }
+}
+
+#[test]
+fn derive_with_visibility_inherit() {
+    let mut flags = fetch_flags();
+
+    check_flag(
+        Some(ExpectedFlag::<bool> {
+            doc: &["True if log messages should also be sent to STDERR"],
+            name: "inherit-to-stderr",
+            placeholder: None,
+            generated_flag: &config::INHERIT_TO_STDERR,
+        }),
+        flags.remove("inherit-to-stderr"),
+    );
+
+    check_flag(
+        Some(ExpectedFlag::<&str> {
+            doc: &["The directory to write log files to"],
+            name: "inherit-dir",
+            placeholder: None,
+            generated_flag: &config::INHERIT_DIR,
+        }),
+        flags.remove("inherit-dir"),
+    );
+}
@@ -0,0 +1,48 @@
+extern crate gflags_derive;
+use gflags_derive::GFlags;
+
+mod common;
+use common::*;
+
+#[test]
+fn derive_with_wrap() {
+    #[derive(GFlags)]
+    #[gflags(prefix = "wrap-", wrap = 40)]
+    #[allow(dead_code)]
+    struct Config {
+        /// The directory to write log files to. It is created if it does not exist
+        dir: String,
+
+        /// Short lines are unchanged
+        /// https://example.com/a/very/long/url/that/is/not/split/anywhere
+        to_stderr: bool,
+    }
+
+    let mut flags = fetch_flags();
+
+    check_flag(
+        Some(ExpectedFlag::<&str> {
+            doc: &[
+                "The directory to write log files to. It",
+                "is created if it does not exist",
+            ],
+            name: "wrap-dir",
+            placeholder: None,
+            generated_flag: &WRAP_DIR,
+        }),
+        flags.remove("wrap-dir"),
+    );
+
+    check_flag(
+        Some(ExpectedFlag::<bool> {
+            doc: &[
+                "Short lines are unchanged",
+                "https://example.com/a/very/long/url/that/is/not/split/anywhere",
+            ],
+            name: "wrap-to-stderr",
+            placeholder: None,
+            generated_flag: &WRAP_TO_STDERR,
+        }),
+        flags.remove("wrap-to-stderr"),
+    );
+}
@@ -0,0 +1,15 @@
+extern crate gflags_derive;
+use gflags_derive::GFlags;
+
+#[derive(GFlags)]
+#[gflags(prefix = "log-", abbrev)]
+#[allow(dead_code)]
+struct Config {
+    /// True if log messages should also be sent to STDERR
+    to_stderr: bool,
+
+    /// True if log messages should also be sent to syslog
+    to_syslog: bool,
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+extern crate gflags_derive;
+use gflags_derive::GFlags;
+
+#[derive(GFlags)]
+#[allow(dead_code)]
+struct Config {
+    /// True if log messages should also be sent to STDERR
+    #[gflags(attr = "doc(hidden)")]
+    to_stderr: bool,
+}
+
+fn main() {}
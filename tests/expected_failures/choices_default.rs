@@ -0,0 +1,12 @@
+extern crate gflags_derive;
+use gflags_derive::GFlags;
+
+#[derive(GFlags)]
+#[allow(dead_code)]
+struct Config {
+    /// Minimum level of messages to log
+    #[gflags(choices = "error, warn, info", default = "debug")]
+    level: String,
+}
+
+fn main() {}
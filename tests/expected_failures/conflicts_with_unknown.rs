@@ -0,0 +1,16 @@
+extern crate gflags_derive;
+use gflags_derive::GFlags;
+
+#[derive(GFlags)]
+#[gflags(generate_validate)]
+#[allow(dead_code)]
+struct Config {
+    /// Only log errors
+    #[gflags(conflicts_with = "verbos")]
+    quiet: bool,
+
+    /// Log everything
+    verbose: bool,
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+extern crate gflags_derive;
+use gflags_derive::GFlags;
+
+#[derive(GFlags)]
+#[allow(dead_code)]
+struct Config {
+    /// Maximum size of a log file, in KiB
+    #[gflags(default = 1024, default_expr = "64 * 1024")]
+    max_size: u32,
+}
+
+fn main() {}
@@ -0,0 +1,17 @@
+extern crate gflags_derive;
+use gflags_derive::GFlags;
+
+use std::sync::LazyLock;
+
+static DEFAULT_DIR: LazyLock<String> = LazyLock::new(|| std::env::temp_dir().display().to_string());
+
+#[derive(GFlags)]
+#[gflags(prefix = "log-")]
+#[allow(dead_code)]
+struct Config {
+    /// The directory to write log files to
+    #[gflags(default_expr = "&*DEFAULT_DIR")]
+    dir: String,
+}
+
+fn main() {}
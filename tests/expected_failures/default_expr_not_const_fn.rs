@@ -0,0 +1,17 @@
+extern crate gflags_derive;
+use gflags_derive::GFlags;
+
+fn default_max_size() -> u32 {
+    64 * 1024
+}
+
+#[derive(GFlags)]
+#[gflags(prefix = "log-")]
+#[allow(dead_code)]
+struct Config {
+    /// Maximum size of a log file, in KiB
+    #[gflags(default_expr = "default_max_size()")]
+    max_size: u32,
+}
+
+fn main() {}
@@ -0,0 +1,20 @@
+extern crate gflags_derive;
+use gflags_derive::GFlags;
+
+#[derive(GFlags)]
+#[gflags(default_from_default)]
+#[allow(dead_code)]
+struct Config {
+    /// String to use for password characters
+    charset: String,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            charset: "ABCDEFGHIJKLMNOPQRSTUVWXYZ".to_string(),
+        }
+    }
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+extern crate gflags_derive;
+use gflags_derive::GFlags;
+
+#[derive(GFlags)]
+#[allow(dead_code)]
+struct Config {
+    /// Number of messages to buffer
+    #[gflags(default = 100u64)]
+    count: u32,
+}
+
+fn main() {}
@@ -0,0 +1,16 @@
+extern crate gflags_derive;
+use gflags_derive::GFlags;
+
+#[derive(GFlags)]
+#[gflags(prefix = "log-")]
+#[allow(dead_code)]
+struct Config {
+    /// The directory to write log files to
+    dir: String,
+
+    /// The directory to write old log files to
+    #[gflags(rename = "dir")]
+    old_dir: String,
+}
+
+fn main() {}
@@ -0,0 +1,14 @@
+extern crate gflags_derive;
+use gflags_derive::GFlags;
+
+#[derive(GFlags)]
+#[allow(dead_code)]
+enum Mode {
+    /// Run normally
+    Normal,
+
+    /// Run with this many threads
+    Threads(u32),
+}
+
+fn main() {}
@@ -0,0 +1,16 @@
+extern crate gflags_derive;
+use gflags_derive::GFlags;
+
+#[derive(GFlags)]
+#[gflags(prefix = "log-")]
+#[allow(dead_code)]
+struct Config {
+    /// Scale to render images at
+    scale_2x: bool,
+
+    /// The directory to write log files to
+    #[gflags(name = "dir-4k")]
+    dir: String,
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+extern crate gflags_derive;
+use gflags_derive::GFlags;
+
+#[derive(GFlags)]
+#[allow(dead_code)]
+struct Config {
+    /// Token to authenticate with
+    #[gflags(placeholder = "TOKEN", from_file)]
+    token: String,
+}
+
+fn main() {}
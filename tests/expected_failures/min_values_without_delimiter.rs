@@ -0,0 +1,13 @@
+extern crate gflags_derive;
+use gflags_derive::GFlags;
+
+#[derive(GFlags)]
+#[gflags(generate_validate)]
+#[allow(dead_code)]
+struct Config {
+    /// The directory to write log files to
+    #[gflags(min_values = 1)]
+    dir: String,
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+extern crate gflags_derive;
+use gflags_derive::GFlags;
+
+#[derive(GFlags)]
+#[allow(dead_code)]
+struct Config {
+    /// The directory to write log files to
+    #[gflags(placeholder = "DIR" visibility = "pub")]
+    dir: String,
+}
+
+fn main() {}
@@ -0,0 +1,13 @@
+extern crate gflags_derive;
+use gflags_derive::GFlags;
+
+#[derive(GFlags)]
+#[allow(dead_code)]
+struct Config {
+    /// True if log messages should also be sent to STDERR
+    #[gflags(name = "stderr")]
+    #[gflags(rename = "to-stderr")]
+    to_stderr: bool,
+}
+
+fn main() {}
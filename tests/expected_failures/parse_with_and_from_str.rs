@@ -0,0 +1,12 @@
+extern crate gflags_derive;
+use gflags_derive::GFlags;
+
+#[derive(GFlags)]
+#[allow(dead_code)]
+struct Config {
+    /// The level to log at
+    #[gflags(parse_with = "parse_level", from_str)]
+    level: Level,
+}
+
+fn main() {}
@@ -0,0 +1,15 @@
+extern crate gflags_derive;
+use gflags_derive::GFlags;
+
+#[derive(GFlags)]
+#[gflags(prefix = "log-", export_help)]
+#[allow(dead_code)]
+struct Config {
+    /// Directory to write log files to
+    dir: String,
+
+    /// Help to show for the log directory
+    dir_help: String,
+}
+
+fn main() {}
@@ -0,0 +1,15 @@
+extern crate gflags_derive;
+use gflags_derive::GFlags;
+
+// This is not compiled with `cfg(test)`, so no flags are generated
+#[derive(GFlags)]
+#[gflags(prefix = "log-", test_only)]
+#[allow(dead_code)]
+struct Config {
+    /// True if log messages should also be sent to STDERR
+    to_stderr: bool,
+}
+
+fn main() {
+    let _ = LOG_TO_STDERR.flag;
+}
@@ -0,0 +1,12 @@
+extern crate gflags_derive;
+use gflags_derive::GFlags;
+
+#[derive(GFlags)]
+#[gflags(transform = "strip_config")]
+#[allow(dead_code)]
+struct Config {
+    /// The directory to write log files to
+    dir_config: String,
+}
+
+fn main() {}
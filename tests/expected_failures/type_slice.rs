@@ -0,0 +1,12 @@
+extern crate gflags_derive;
+use gflags_derive::GFlags;
+
+#[derive(GFlags)]
+#[allow(dead_code)]
+struct Config {
+    /// The key to sign requests with
+    #[gflags(type = "&[u8]")]
+    key: Vec<u8>,
+}
+
+fn main() {}
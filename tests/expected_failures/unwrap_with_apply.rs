@@ -0,0 +1,18 @@
+extern crate gflags_derive;
+use gflags_derive::GFlags;
+
+#[allow(dead_code)]
+enum Maybe<T> {
+    Just(T),
+    Nothing,
+}
+
+#[derive(GFlags)]
+#[gflags(unwrap = "Maybe", generate_apply)]
+#[allow(dead_code)]
+struct Config {
+    /// The directory to write log files to
+    dir: Maybe<String>,
+}
+
+fn main() {}
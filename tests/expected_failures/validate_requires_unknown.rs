@@ -0,0 +1,16 @@
+extern crate gflags_derive;
+use gflags_derive::GFlags;
+
+#[derive(GFlags)]
+#[gflags(generate_validate)]
+#[allow(dead_code)]
+struct Config {
+    /// Maximum size of a log file, in KiB
+    #[gflags(requires = "dri")]
+    max_size: u32,
+
+    /// The directory to write log files to
+    dir: String,
+}
+
+fn main() {}
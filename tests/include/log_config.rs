@@ -0,0 +1,22 @@
+// Mimics a file generated by `prost-build`, with docs as `#[doc]` attributes,
+// and another derive's attributes on the struct and fields
+
+/// Configuration for logging
+#[derive(Clone, Debug, Default, PartialEq, serde::Deserialize)]
+#[serde(default)]
+#[derive(GFlags)]
+#[gflags(prefix = "inc-")]
+pub struct LogConfig {
+    #[doc = " True if log messages should also be sent to STDERR"]
+    #[serde(rename = "toStderr")]
+    pub to_stderr: bool,
+    #[doc = " The directory to write log files to."]
+    #[doc = ""]
+    #[doc = " Created if it does not exist."]
+    #[serde(rename = "dir")]
+    #[gflags(placeholder = "DIR")]
+    pub dir: ::std::string::String,
+    #[doc = "Maximum size of a log file, in KiB"]
+    #[serde(rename = "maxSize")]
+    pub max_size: u32,
+}